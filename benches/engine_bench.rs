@@ -1,41 +1,34 @@
 use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use lb_sim::algorithms::StrategyRegistry;
 use lb_sim::engine::run_simulation;
-use lb_sim::models::{AlgoConfig, RequestProfile, ServerConfig, SimConfig, TieBreakConfig};
+use lb_sim::models::{AlgoConfig, RequestProfile, ServerConfig, SimConfig};
 
 const REQUESTS: usize = 1_000;
 const SERVERS: usize = 8;
 
 fn build_servers(count: usize) -> Vec<ServerConfig> {
     (0..count)
-        .map(|idx| ServerConfig {
-            name: format!("srv-{}", idx),
-            base_latency_ms: 10 + idx as u64,
-            weight: 1,
-        })
+        .map(|idx| ServerConfig::new(format!("srv-{}", idx), 10 + idx as u64))
         .collect()
 }
 
 fn build_config(algo: AlgoConfig) -> SimConfig {
-    SimConfig {
-        servers: build_servers(SERVERS),
-        requests: RequestProfile::FixedCount(REQUESTS),
+    SimConfig::new(
+        build_servers(SERVERS),
+        RequestProfile::FixedCount(REQUESTS),
         algo,
-        tie_break: TieBreakConfig::Stable,
-        seed: None,
-    }
+    )
 }
 
 fn bench_engine(c: &mut Criterion) {
     let mut group = c.benchmark_group("engine");
     let size_label = format!("{}x{}", REQUESTS, SERVERS);
-    let algos = [
-        AlgoConfig::RoundRobin,
-        AlgoConfig::WeightedRoundRobin,
-        AlgoConfig::LeastConnections,
-        AlgoConfig::LeastResponseTime,
-    ];
+    let registry = StrategyRegistry::builtin();
 
-    for algo in algos {
+    for algo in registry
+        .iter()
+        .map(|registration| AlgoConfig::new(registration.name()))
+    {
         let algo_label = algo.to_string();
         group.bench_with_input(
             BenchmarkId::new(algo_label, &size_label),
@@ -16,6 +16,9 @@ fn build_events(count: usize) -> Vec<ScheduledEvent> {
                     Event::RequestArrival(Request {
                         id: idx,
                         arrival_time_ms: time_ms,
+                        session_id: None,
+                        session_end: false,
+                        key: None,
                     }),
                 )
             } else {
@@ -24,6 +27,8 @@ fn build_events(count: usize) -> Vec<ScheduledEvent> {
                     Event::RequestComplete {
                         server_id: ServerId::from(idx % 8),
                         request_id: idx,
+                        balancer_id: 0,
+                        latency_ms: 0,
                     },
                 )
             }
@@ -1,6 +1,6 @@
 use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
-use lb_sim::algorithms::{build_strategy, SelectionContext};
-use lb_sim::models::AlgoConfig;
+use lb_sim::algorithms::{SelectionContext, StrategyRegistration, StrategyRegistry};
+use lb_sim::models::{AlgoConfig, RequestProfile, SimConfig, TieBreakConfig};
 use lb_sim::state::{ServerId, ServerState};
 use rand::rngs::StdRng;
 use rand::SeedableRng;
@@ -19,6 +19,8 @@ fn build_servers(count: usize) -> Vec<ServerState> {
             pick_count: (idx % 5) as u32,
             in_flight: 0,
             next_available_ms: 0,
+            network_latency_ms: 0,
+            healthy: true,
         })
         .collect()
 }
@@ -26,24 +28,27 @@ fn build_servers(count: usize) -> Vec<ServerState> {
 fn bench_selection(c: &mut Criterion) {
     let mut group = c.benchmark_group("selection");
     let size_label = format!("{}x{}", ITERATIONS, SERVERS);
-    let algos = [
-        AlgoConfig::RoundRobin,
-        AlgoConfig::WeightedRoundRobin,
-        AlgoConfig::LeastConnections,
-        AlgoConfig::LeastResponseTime,
-    ];
+    let registry = StrategyRegistry::builtin();
+    let config = SimConfig {
+        tie_break: TieBreakConfig::Seeded,
+        seed: Some(1),
+        ..SimConfig::new(
+            Vec::new(),
+            RequestProfile::FixedCount(ITERATIONS),
+            AlgoConfig::new("round-robin"),
+        )
+    };
 
-    for algo in algos {
-        let algo_label = algo.to_string();
+    for registration in registry.iter() {
         group.bench_with_input(
-            BenchmarkId::new(algo_label, &size_label),
-            &algo,
-            |b, algo: &AlgoConfig| {
+            BenchmarkId::new(registration.name(), &size_label),
+            registration,
+            |b, registration: &StrategyRegistration| {
                 b.iter_batched(
                     || {
                         let servers = build_servers(SERVERS);
                         let rng = StdRng::seed_from_u64(1);
-                        let strategy = build_strategy(algo.clone());
+                        let strategy = registration.build(&config);
                         (servers, rng, strategy)
                     },
                     |(servers, mut rng, mut strategy)| {
@@ -51,6 +56,9 @@ fn bench_selection(c: &mut Criterion) {
                             servers: &servers,
                             time_ms: 0,
                             rng: &mut rng,
+                            session_id: None,
+                            request_key: None,
+                            tie_break: TieBreakConfig::Seeded,
                         };
                         for _ in 0..ITERATIONS {
                             let selection = strategy.select(&mut ctx);
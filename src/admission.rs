@@ -0,0 +1,194 @@
+use crate::models::{AdaptiveLimitConfig, AdmissionConfig, TokenBucketConfig};
+use crate::state::RejectReason;
+
+/// Admission control in front of the balancers. One controller is shared by
+/// every balancer, so limits apply to all requests admitted and not yet
+/// complete, whichever balancer routed them.
+pub struct AdmissionController {
+    max_in_flight: Option<u32>,
+    bucket: Option<TokenBucket>,
+    adaptive: Option<AdaptiveLimit>,
+    in_flight: u32,
+}
+
+impl AdmissionController {
+    pub fn new(config: &AdmissionConfig) -> Self {
+        Self {
+            max_in_flight: config.max_in_flight,
+            bucket: config.rate_limit.as_ref().map(TokenBucket::new),
+            adaptive: config.adaptive.as_ref().map(AdaptiveLimit::new),
+            in_flight: 0,
+        }
+    }
+
+    /// Decides whether a request arriving at `time_ms` is dispatched. An
+    /// admitted request counts as in flight until `on_complete`.
+    pub fn admit(&mut self, time_ms: u64) -> Result<(), RejectReason> {
+        if self
+            .max_in_flight
+            .is_some_and(|limit| self.in_flight >= limit)
+        {
+            return Err(RejectReason::MaxInFlight);
+        }
+        if let Some(adaptive) = &self.adaptive {
+            if f64::from(self.in_flight) >= adaptive.limit.floor() {
+                return Err(RejectReason::ConcurrencyLimit);
+            }
+        }
+        if let Some(bucket) = &mut self.bucket {
+            if !bucket.try_take(time_ms) {
+                return Err(RejectReason::RateLimit);
+            }
+        }
+        self.in_flight += 1;
+        Ok(())
+    }
+
+    pub fn on_complete(&mut self, latency_ms: u64) {
+        if let Some(adaptive) = &mut self.adaptive {
+            adaptive.on_sample(latency_ms, self.in_flight);
+        }
+        self.in_flight = self.in_flight.saturating_sub(1);
+    }
+
+    /// An admitted request failed without a response (its server went
+    /// down); it no longer counts as in flight.
+    pub fn on_failure(&mut self) {
+        self.in_flight = self.in_flight.saturating_sub(1);
+    }
+
+    pub fn concurrency_limit(&self) -> Option<f64> {
+        self.adaptive.as_ref().map(|adaptive| adaptive.limit)
+    }
+}
+
+struct TokenBucket {
+    rate_per_ms: f64,
+    capacity: f64,
+    tokens: f64,
+    updated_ms: u64,
+}
+
+impl TokenBucket {
+    fn new(config: &TokenBucketConfig) -> Self {
+        let capacity = f64::from(config.burst.max(1));
+        Self {
+            rate_per_ms: config.rate / 1000.0,
+            capacity,
+            tokens: capacity,
+            updated_ms: 0,
+        }
+    }
+
+    fn try_take(&mut self, time_ms: u64) -> bool {
+        let elapsed = time_ms.saturating_sub(self.updated_ms) as f64;
+        self.tokens = (self.tokens + elapsed * self.rate_per_ms).min(self.capacity);
+        self.updated_ms = time_ms;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// AIMD concurrency limit in the style of Netflix concurrency-limits: a
+/// completion slower than `latency_threshold_ms` multiplies the limit by
+/// `backoff`; otherwise the limit grows by one while at least half of it is
+/// in use.
+struct AdaptiveLimit {
+    limit: f64,
+    min_limit: f64,
+    max_limit: f64,
+    latency_threshold_ms: u64,
+    backoff: f64,
+}
+
+impl AdaptiveLimit {
+    fn new(config: &AdaptiveLimitConfig) -> Self {
+        Self {
+            limit: f64::from(config.initial_limit),
+            min_limit: f64::from(config.min_limit),
+            max_limit: f64::from(config.max_limit),
+            latency_threshold_ms: config.latency_threshold_ms,
+            backoff: config.backoff,
+        }
+    }
+
+    fn on_sample(&mut self, latency_ms: u64, in_flight: u32) {
+        if latency_ms > self.latency_threshold_ms {
+            self.limit *= self.backoff;
+        } else if f64::from(in_flight) * 2.0 >= self.limit {
+            self.limit += 1.0;
+        }
+        self.limit = self.limit.clamp(self.min_limit, self.max_limit);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> AdmissionConfig {
+        AdmissionConfig {
+            max_in_flight: None,
+            rate_limit: None,
+            adaptive: None,
+        }
+    }
+
+    #[test]
+    fn max_in_flight_rejects_until_a_request_completes() {
+        let mut controller = AdmissionController::new(&AdmissionConfig {
+            max_in_flight: Some(2),
+            ..config()
+        });
+
+        assert_eq!(controller.admit(0), Ok(()));
+        assert_eq!(controller.admit(0), Ok(()));
+        assert_eq!(controller.admit(0), Err(RejectReason::MaxInFlight));
+        controller.on_complete(10);
+        assert_eq!(controller.admit(10), Ok(()));
+    }
+
+    #[test]
+    fn token_bucket_refills_at_configured_rate() {
+        let mut controller = AdmissionController::new(&AdmissionConfig {
+            rate_limit: Some(TokenBucketConfig {
+                rate: 100.0,
+                burst: 2,
+            }),
+            ..config()
+        });
+
+        assert_eq!(controller.admit(0), Ok(()));
+        assert_eq!(controller.admit(0), Ok(()));
+        assert_eq!(controller.admit(5), Err(RejectReason::RateLimit));
+        assert_eq!(controller.admit(10), Ok(()));
+    }
+
+    #[test]
+    fn adaptive_limit_backs_off_on_slow_completions() {
+        let mut controller = AdmissionController::new(&AdmissionConfig {
+            adaptive: Some(AdaptiveLimitConfig {
+                initial_limit: 4,
+                min_limit: 1,
+                max_limit: 100,
+                latency_threshold_ms: 50,
+                backoff: 0.5,
+            }),
+            ..config()
+        });
+
+        for _ in 0..4 {
+            assert_eq!(controller.admit(0), Ok(()));
+        }
+        assert_eq!(controller.admit(0), Err(RejectReason::ConcurrencyLimit));
+        controller.on_complete(10);
+        assert_eq!(controller.concurrency_limit(), Some(5.0));
+        controller.on_complete(80);
+        assert_eq!(controller.concurrency_limit(), Some(2.5));
+        assert_eq!(controller.admit(0), Err(RejectReason::ConcurrencyLimit));
+    }
+}
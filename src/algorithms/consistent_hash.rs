@@ -0,0 +1,201 @@
+use rand::Rng;
+
+use crate::algorithms::{SamplingRng, Selection, SelectionContext, SelectionStrategy};
+use crate::state::{ServerId, ServerState};
+
+pub const DEFAULT_HASH_EPSILON: f64 = 0.25;
+pub const DEFAULT_VIRTUAL_NODES: usize = 100;
+
+/// Consistent hashing with bounded loads (Mirrokni, Thorup, Zadimoghaddam).
+///
+/// Each server is placed on a hash ring `virtual_nodes` times, keyed by its
+/// name so placements survive pool changes. A request's key is hashed onto
+/// the ring and walks clockwise to the first server whose active connections
+/// are below `ceil((1 + epsilon) * (total_active + 1) / servers)`. Requests
+/// without a key hash on their session id, or else on a random draw.
+pub struct ConsistentHashStrategy {
+    epsilon: f64,
+    virtual_nodes: usize,
+    ring: Vec<(u64, usize)>,
+    ring_ids: Vec<ServerId>,
+    sampling: SamplingRng,
+}
+
+impl ConsistentHashStrategy {
+    pub fn new(epsilon: f64, virtual_nodes: usize) -> Self {
+        Self {
+            epsilon: epsilon.max(0.0),
+            virtual_nodes: virtual_nodes.max(1),
+            ring: Vec::new(),
+            ring_ids: Vec::new(),
+            sampling: SamplingRng::default(),
+        }
+    }
+
+    fn rebuild_ring(&mut self, servers: &[ServerState]) {
+        self.ring.clear();
+        self.ring_ids.clear();
+        for (position, server) in servers.iter().enumerate() {
+            let name_hash = fnv1a(server.name.as_bytes());
+            for replica in 0..self.virtual_nodes as u64 {
+                self.ring
+                    .push((splitmix64(name_hash ^ splitmix64(replica)), position));
+            }
+            self.ring_ids.push(server.id);
+        }
+        self.ring.sort_unstable();
+    }
+
+    fn capacity(&self, servers: &[ServerState]) -> u64 {
+        let total = servers
+            .iter()
+            .map(|server| u64::from(server.active_connections))
+            .sum::<u64>();
+        let average = (total + 1) as f64 / servers.len() as f64;
+        ((1.0 + self.epsilon) * average).ceil() as u64
+    }
+}
+
+impl Default for ConsistentHashStrategy {
+    fn default() -> Self {
+        Self::new(DEFAULT_HASH_EPSILON, DEFAULT_VIRTUAL_NODES)
+    }
+}
+
+impl SelectionStrategy for ConsistentHashStrategy {
+    fn select(&mut self, ctx: &mut SelectionContext) -> Selection {
+        let stale = self.ring_ids.len() != ctx.servers.len()
+            || self
+                .ring_ids
+                .iter()
+                .zip(ctx.servers)
+                .any(|(id, server)| *id != server.id);
+        if stale {
+            self.rebuild_ring(ctx.servers);
+        }
+
+        let key = match ctx.request_key.or(ctx.session_id) {
+            Some(key) => key,
+            None => self.sampling.rng(ctx).gen(),
+        };
+        let point = splitmix64(key);
+        let capacity = self.capacity(ctx.servers);
+        let start = self.ring.partition_point(|&(hash, _)| hash < point);
+
+        let mut choice = self.ring[start % self.ring.len()].1;
+        for offset in 0..self.ring.len() {
+            let position = self.ring[(start + offset) % self.ring.len()].1;
+            if u64::from(ctx.servers[position].active_connections) < capacity {
+                choice = position;
+                break;
+            }
+        }
+
+        Selection {
+            server_id: ServerId::from(choice),
+            score: None,
+        }
+    }
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TieBreakConfig;
+    use rand::SeedableRng;
+
+    fn server(id: usize, active_connections: u32) -> ServerState {
+        ServerState {
+            id: ServerId::from(id),
+            name: format!("s{}", id),
+            base_latency_ms: 10,
+            weight: 1,
+            active_connections,
+            pick_count: 0,
+            in_flight: active_connections,
+            next_available_ms: 0,
+            network_latency_ms: 0,
+            healthy: true,
+        }
+    }
+
+    fn pick(strategy: &mut ConsistentHashStrategy, servers: &[ServerState], key: u64) -> ServerId {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let mut ctx = SelectionContext {
+            servers,
+            time_ms: 0,
+            rng: &mut rng,
+            session_id: None,
+            request_key: Some(key),
+            tie_break: TieBreakConfig::Seeded,
+        };
+        strategy.select(&mut ctx).server_id
+    }
+
+    #[test]
+    fn consistent_hash_maps_keys_to_stable_servers() {
+        let servers: Vec<ServerState> = (0..4).map(|id| server(id, 0)).collect();
+        let mut strategy = ConsistentHashStrategy::default();
+
+        let first: Vec<ServerId> = (0..64)
+            .map(|key| pick(&mut strategy, &servers, key))
+            .collect();
+        let second: Vec<ServerId> = (0..64)
+            .map(|key| pick(&mut strategy, &servers, key))
+            .collect();
+
+        assert_eq!(first, second);
+        for id in 0..4 {
+            assert!(first.contains(&ServerId::from(id)));
+        }
+    }
+
+    #[test]
+    fn consistent_hash_moves_few_keys_when_a_server_is_removed() {
+        let servers: Vec<ServerState> = (0..4).map(|id| server(id, 0)).collect();
+        let mut strategy = ConsistentHashStrategy::default();
+        let before: Vec<ServerId> = (0..400)
+            .map(|key| pick(&mut strategy, &servers, key))
+            .collect();
+
+        let remaining = servers[..3].to_vec();
+        let after: Vec<ServerId> = (0..400)
+            .map(|key| pick(&mut strategy, &remaining, key))
+            .collect();
+
+        for (old, new) in before.iter().zip(&after) {
+            if *old != ServerId::from(3) {
+                assert_eq!(old, new);
+            }
+        }
+    }
+
+    #[test]
+    fn consistent_hash_overflows_past_servers_at_the_load_bound() {
+        let idle: Vec<ServerState> = (0..4).map(|id| server(id, 0)).collect();
+        let mut strategy = ConsistentHashStrategy::new(0.0, 50);
+        let home = pick(&mut strategy, &idle, 7);
+
+        // 8 active + this request over 4 servers caps each server at 3.
+        let mut loaded: Vec<ServerState> = (0..4).map(|id| server(id, 2)).collect();
+        loaded[usize::from(home)].active_connections = 3;
+        loaded[(usize::from(home) + 1) % 4].active_connections = 1;
+        let overflow = pick(&mut strategy, &loaded, 7);
+
+        assert_ne!(overflow, home);
+        assert!(loaded[usize::from(overflow)].active_connections < 3);
+    }
+}
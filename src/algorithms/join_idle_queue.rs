@@ -0,0 +1,151 @@
+use rand::Rng;
+use std::collections::VecDeque;
+
+use crate::algorithms::{SamplingRng, Selection, SelectionContext, SelectionStrategy};
+use crate::state::{ServerId, ServerState};
+
+/// Join-Idle-Queue: servers announce themselves as idle through completion
+/// events. A request goes to the oldest announced server that is still idle,
+/// or to a uniformly random server when the idle queue is empty.
+#[derive(Default)]
+pub struct JoinIdleQueueStrategy {
+    idle: VecDeque<ServerId>,
+    queued: Vec<bool>,
+    initialized: bool,
+    sampling: SamplingRng,
+}
+
+impl JoinIdleQueueStrategy {
+    fn announce(&mut self, server_id: ServerId) {
+        let idx = usize::from(server_id);
+        if self.queued.len() <= idx {
+            self.queued.resize(idx + 1, false);
+        }
+        if !self.queued[idx] {
+            self.queued[idx] = true;
+            self.idle.push_back(server_id);
+        }
+    }
+}
+
+impl SelectionStrategy for JoinIdleQueueStrategy {
+    fn select(&mut self, ctx: &mut SelectionContext) -> Selection {
+        if !self.initialized {
+            self.initialized = true;
+            for server in ctx.servers {
+                self.announce(server.id);
+            }
+        }
+
+        while let Some(server_id) = self.idle.pop_front() {
+            self.queued[usize::from(server_id)] = false;
+            let idle = ctx
+                .servers
+                .iter()
+                .position(|server| server.id == server_id && server.in_flight == 0);
+            if let Some(position) = idle {
+                return Selection {
+                    server_id: ServerId::from(position),
+                    score: None,
+                };
+            }
+        }
+
+        let len = ctx.servers.len();
+        Selection {
+            server_id: ServerId::from(self.sampling.rng(ctx).gen_range(0..len)),
+            score: None,
+        }
+    }
+
+    fn on_complete(&mut self, server: &ServerState) {
+        if server.in_flight == 0 {
+            self.announce(server.id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TieBreakConfig;
+    use rand::SeedableRng;
+
+    fn server(id: usize, in_flight: u32) -> ServerState {
+        ServerState {
+            id: ServerId::from(id),
+            name: format!("s{}", id),
+            base_latency_ms: 10,
+            weight: 1,
+            active_connections: in_flight,
+            pick_count: 0,
+            in_flight,
+            next_available_ms: 0,
+            network_latency_ms: 0,
+            healthy: true,
+        }
+    }
+
+    #[test]
+    fn join_idle_queue_prefers_announced_idle_servers() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let mut strategy = JoinIdleQueueStrategy::default();
+
+        let servers = vec![server(0, 0), server(1, 0)];
+        let mut ctx = SelectionContext {
+            servers: &servers,
+            time_ms: 0,
+            rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
+        };
+        assert_eq!(strategy.select(&mut ctx).server_id, ServerId::from(0));
+
+        let servers = vec![server(0, 1), server(1, 0)];
+        let mut ctx = SelectionContext {
+            servers: &servers,
+            time_ms: 1,
+            rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
+        };
+        assert_eq!(strategy.select(&mut ctx).server_id, ServerId::from(1));
+
+        let servers = vec![server(0, 0), server(1, 1)];
+        strategy.on_complete(&servers[0]);
+        let mut ctx = SelectionContext {
+            servers: &servers,
+            time_ms: 2,
+            rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
+        };
+        assert_eq!(strategy.select(&mut ctx).server_id, ServerId::from(0));
+    }
+
+    #[test]
+    fn join_idle_queue_falls_back_to_random_when_no_server_is_idle() {
+        let servers = vec![server(0, 1), server(1, 1), server(2, 1)];
+        let mut rng = rand::rngs::StdRng::seed_from_u64(5);
+        let expected = rng.gen_range(0..servers.len());
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(5);
+        let mut strategy = JoinIdleQueueStrategy::default();
+        let mut ctx = SelectionContext {
+            servers: &servers,
+            time_ms: 0,
+            rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
+        };
+
+        assert_eq!(
+            strategy.select(&mut ctx).server_id,
+            ServerId::from(expected)
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use rand::seq::index;
+use rand::Rng;
+
+use crate::algorithms::{SamplingRng, Selection, SelectionContext, SelectionStrategy};
+use crate::state::ServerId;
+
+pub const DEFAULT_JSQ_CHOICES: usize = 2;
+
+/// Join-Shortest-Queue(d): samples `d` distinct servers and picks the one
+/// with the shortest queue, breaking ties with the tie-break RNG. A server's
+/// queue length is its requests in flight, which already counts the ones
+/// queued behind the request in service.
+pub struct JoinShortestQueueStrategy {
+    choices: usize,
+    candidates: Vec<usize>,
+    sampling: SamplingRng,
+}
+
+impl JoinShortestQueueStrategy {
+    pub fn new(choices: usize) -> Self {
+        Self {
+            choices: choices.max(1),
+            candidates: Vec::new(),
+            sampling: SamplingRng::default(),
+        }
+    }
+}
+
+impl Default for JoinShortestQueueStrategy {
+    fn default() -> Self {
+        Self::new(DEFAULT_JSQ_CHOICES)
+    }
+}
+
+impl SelectionStrategy for JoinShortestQueueStrategy {
+    fn select(&mut self, ctx: &mut SelectionContext) -> Selection {
+        let len = ctx.servers.len();
+        let sampled = index::sample(self.sampling.rng(ctx), len, self.choices.min(len));
+
+        let mut best = u32::MAX;
+        self.candidates.clear();
+        for idx in sampled.iter() {
+            let queue = ctx.servers[idx].in_flight;
+            if queue < best {
+                best = queue;
+                self.candidates.clear();
+                self.candidates.push(idx);
+            } else if queue == best {
+                self.candidates.push(idx);
+            }
+        }
+        self.candidates.sort_unstable();
+
+        let choice = if self.candidates.len() == 1 {
+            self.candidates[0]
+        } else {
+            let pick = ctx.rng.gen_range(0..self.candidates.len());
+            self.candidates[pick]
+        };
+
+        Selection {
+            server_id: ServerId::from(choice),
+            score: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TieBreakConfig;
+    use crate::state::{ServerId, ServerState};
+    use rand::SeedableRng;
+
+    fn server(id: usize, in_flight: u32, next_available_ms: u64) -> ServerState {
+        ServerState {
+            id: ServerId::from(id),
+            name: format!("s{}", id),
+            base_latency_ms: 10,
+            weight: 1,
+            active_connections: in_flight,
+            pick_count: 0,
+            in_flight,
+            next_available_ms,
+            network_latency_ms: 0,
+            healthy: true,
+        }
+    }
+
+    #[test]
+    fn join_shortest_queue_with_full_sampling_picks_shortest_queue() {
+        let servers = vec![server(0, 2, 20), server(1, 3, 30), server(2, 1, 10)];
+        let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+        let mut strategy = JoinShortestQueueStrategy::new(3);
+        let mut ctx = SelectionContext {
+            servers: &servers,
+            time_ms: 0,
+            rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
+        };
+
+        assert_eq!(strategy.select(&mut ctx).server_id, ServerId::from(2));
+    }
+
+    #[test]
+    fn join_shortest_queue_counts_queued_requests_once() {
+        // Server 0 has three requests dispatched at 0 ms, all queued or in
+        // service; server 1 has finished serving four whose responses are
+        // still crossing the network. Queued work is already in `in_flight`.
+        let servers = vec![server(0, 3, 30), server(1, 4, 0)];
+        let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+        let mut strategy = JoinShortestQueueStrategy::new(2);
+        let mut ctx = SelectionContext {
+            servers: &servers,
+            time_ms: 0,
+            rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
+        };
+
+        assert_eq!(strategy.select(&mut ctx).server_id, ServerId::from(0));
+    }
+
+    #[test]
+    fn join_shortest_queue_samples_under_stable_tie_breaks() {
+        let servers = vec![
+            server(0, 5, 0),
+            server(1, 0, 0),
+            server(2, 0, 0),
+            server(3, 0, 0),
+        ];
+        // The stable tie-break RNG always yields zero.
+        let mut rng = rand::rngs::mock::StepRng::new(0, 0);
+        let mut strategy = JoinShortestQueueStrategy::default();
+        let picks: std::collections::BTreeSet<ServerId> = (0..20)
+            .map(|_| {
+                let mut ctx = SelectionContext {
+                    servers: &servers,
+                    time_ms: 0,
+                    rng: &mut rng,
+                    session_id: None,
+                    request_key: None,
+                    tie_break: TieBreakConfig::Stable,
+                };
+                strategy.select(&mut ctx).server_id
+            })
+            .collect();
+
+        assert!(picks.len() > 1, "stable runs should not pin the sample");
+        assert!(!picks.contains(&ServerId::from(0)));
+    }
+
+    #[test]
+    fn join_shortest_queue_only_considers_sampled_servers() {
+        let servers = vec![
+            server(0, 4, 0),
+            server(1, 3, 0),
+            server(2, 2, 0),
+            server(3, 0, 0),
+        ];
+        let mut rng = rand::rngs::StdRng::seed_from_u64(11);
+        let sampled = index::sample(&mut rng, servers.len(), 2).into_vec();
+        let expected = sampled
+            .iter()
+            .copied()
+            .min_by_key(|&idx| servers[idx].in_flight)
+            .expect("two servers sampled");
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(11);
+        let mut strategy = JoinShortestQueueStrategy::default();
+        let mut ctx = SelectionContext {
+            servers: &servers,
+            time_ms: 0,
+            rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
+        };
+
+        assert_eq!(
+            strategy.select(&mut ctx).server_id,
+            ServerId::from(expected)
+        );
+    }
+}
@@ -44,6 +44,7 @@ impl SelectionStrategy for LeastConnectionsStrategy {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::TieBreakConfig;
     use crate::state::{ServerId, ServerState};
     use rand::SeedableRng;
 
@@ -59,6 +60,8 @@ mod tests {
                 pick_count: 0,
                 in_flight: 0,
                 next_available_ms: 0,
+                network_latency_ms: 0,
+                healthy: true,
             },
             ServerState {
                 id: ServerId::from(1),
@@ -69,6 +72,8 @@ mod tests {
                 pick_count: 0,
                 in_flight: 0,
                 next_available_ms: 0,
+                network_latency_ms: 0,
+                healthy: true,
             },
             ServerState {
                 id: ServerId::from(2),
@@ -79,6 +84,8 @@ mod tests {
                 pick_count: 0,
                 in_flight: 0,
                 next_available_ms: 0,
+                network_latency_ms: 0,
+                healthy: true,
             },
         ];
         let mut rng = rand::rngs::StdRng::seed_from_u64(1);
@@ -87,6 +94,9 @@ mod tests {
             servers: &servers,
             time_ms: 0,
             rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
         };
 
         assert_eq!(strategy.select(&mut ctx).server_id, ServerId::from(1));
@@ -104,6 +114,8 @@ mod tests {
                 pick_count: 0,
                 in_flight: 0,
                 next_available_ms: 0,
+                network_latency_ms: 0,
+                healthy: true,
             },
             ServerState {
                 id: ServerId::from(1),
@@ -114,6 +126,8 @@ mod tests {
                 pick_count: 0,
                 in_flight: 0,
                 next_available_ms: 0,
+                network_latency_ms: 0,
+                healthy: true,
             },
             ServerState {
                 id: ServerId::from(2),
@@ -124,6 +138,8 @@ mod tests {
                 pick_count: 0,
                 in_flight: 0,
                 next_available_ms: 0,
+                network_latency_ms: 0,
+                healthy: true,
             },
         ];
         let candidates = [0usize, 1, 2];
@@ -139,6 +155,9 @@ mod tests {
             servers: &servers,
             time_ms: 0,
             rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
         };
 
         assert_eq!(
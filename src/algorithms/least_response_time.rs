@@ -20,8 +20,9 @@ impl SelectionStrategy for LeastResponseTimeStrategy {
         for (idx, server) in ctx.servers.iter().enumerate() {
             let score = server
                 .next_available_ms
-                .max(ctx.time_ms)
-                .saturating_add(server.base_latency_ms);
+                .max(ctx.time_ms.saturating_add(server.network_latency_ms))
+                .saturating_add(server.base_latency_ms)
+                .saturating_add(server.network_latency_ms);
             if score < min_score {
                 min_score = score;
                 self.candidates.clear();
@@ -48,6 +49,7 @@ impl SelectionStrategy for LeastResponseTimeStrategy {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::TieBreakConfig;
     use crate::state::{ServerId, ServerState};
     use rand::SeedableRng;
 
@@ -63,6 +65,8 @@ mod tests {
                 pick_count: 0,
                 in_flight: 0,
                 next_available_ms: 0,
+                network_latency_ms: 0,
+                healthy: true,
             },
             ServerState {
                 id: ServerId::from(1),
@@ -73,6 +77,8 @@ mod tests {
                 pick_count: 2,
                 in_flight: 0,
                 next_available_ms: 50,
+                network_latency_ms: 0,
+                healthy: true,
             },
             ServerState {
                 id: ServerId::from(2),
@@ -83,6 +89,8 @@ mod tests {
                 pick_count: 0,
                 in_flight: 0,
                 next_available_ms: 0,
+                network_latency_ms: 0,
+                healthy: true,
             },
         ];
         let mut rng = rand::rngs::StdRng::seed_from_u64(1);
@@ -91,6 +99,9 @@ mod tests {
             servers: &servers,
             time_ms: 0,
             rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
         };
 
         let selection = strategy.select(&mut ctx);
@@ -110,6 +121,8 @@ mod tests {
                 pick_count: 0,
                 in_flight: 0,
                 next_available_ms: 0,
+                network_latency_ms: 0,
+                healthy: true,
             },
             ServerState {
                 id: ServerId::from(1),
@@ -120,6 +133,8 @@ mod tests {
                 pick_count: 1,
                 in_flight: 0,
                 next_available_ms: 10,
+                network_latency_ms: 0,
+                healthy: true,
             },
             ServerState {
                 id: ServerId::from(2),
@@ -130,6 +145,8 @@ mod tests {
                 pick_count: 0,
                 in_flight: 0,
                 next_available_ms: 0,
+                network_latency_ms: 0,
+                healthy: true,
             },
         ];
         let candidates = [0usize, 1];
@@ -145,6 +162,9 @@ mod tests {
             servers: &servers,
             time_ms: 0,
             rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
         };
 
         let selection = strategy.select(&mut ctx);
@@ -1,20 +1,73 @@
+mod consistent_hash;
+mod join_idle_queue;
+mod join_shortest_queue;
 mod least_connections;
 mod least_response_time;
+mod priority_tiers;
+mod random;
+mod registry;
 mod round_robin;
+#[cfg(feature = "scripting")]
+mod script;
+mod smooth_weighted_round_robin;
+mod sticky;
+mod subset;
+#[cfg(feature = "wasm")]
+mod wasm_plugin;
+mod weighted_least_connections;
+mod weighted_least_response_time;
+mod weighted_random;
 mod weighted_round_robin;
+mod zone_aware;
 
-use rand::RngCore;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
 
-use crate::models::AlgoConfig;
+use crate::models::{SimConfig, TieBreakConfig};
 use crate::state::{ServerId, ServerState};
 
+pub use consistent_hash::{ConsistentHashStrategy, DEFAULT_HASH_EPSILON, DEFAULT_VIRTUAL_NODES};
+pub use join_idle_queue::JoinIdleQueueStrategy;
+pub use join_shortest_queue::{JoinShortestQueueStrategy, DEFAULT_JSQ_CHOICES};
 pub use least_connections::LeastConnectionsStrategy;
 pub use least_response_time::LeastResponseTimeStrategy;
+pub use priority_tiers::PriorityTierStrategy;
+pub use random::RandomStrategy;
+pub use registry::{
+    ParamKind, ParamSpec, StrategyConstructor, StrategyRegistration, StrategyRegistry,
+};
 pub use round_robin::RoundRobinStrategy;
+#[cfg(feature = "scripting")]
+pub use script::{ScriptStrategy, SCRIPT_MAX_OPERATIONS};
+pub use smooth_weighted_round_robin::SmoothWeightedRoundRobinStrategy;
+pub use sticky::StickyStrategy;
+#[cfg(feature = "wasm")]
+pub use wasm_plugin::{
+    WasmStrategy, PLUGIN_ABI_VERSION, PLUGIN_FUEL_PER_SELECT, PLUGIN_SERVER_RECORD_SIZE,
+};
+pub use weighted_least_connections::WeightedLeastConnectionsStrategy;
+pub use weighted_least_response_time::WeightedLeastResponseTimeStrategy;
+pub use weighted_random::WeightedRandomStrategy;
 pub use weighted_round_robin::WeightedRoundRobinStrategy;
+pub use zone_aware::ZoneAwareStrategy;
 
 pub trait SelectionStrategy {
     fn select(&mut self, ctx: &mut SelectionContext) -> Selection;
+
+    /// Called when a request dispatched by this strategy's balancer completes,
+    /// with the balancer's updated view of that server.
+    fn on_complete(&mut self, _server: &ServerState) {}
+
+    /// Called after the last request of a session has been routed, so
+    /// strategies can drop per-session state.
+    fn on_session_end(&mut self, _session_id: u64) {}
+
+    /// Takes the error raised by the last `select`, if any, such as a user
+    /// script that failed. The engine aborts the run with it; the selection
+    /// returned alongside an error must still be a valid server index.
+    fn take_error(&mut self) -> Option<String> {
+        None
+    }
 }
 
 pub struct SelectionContext<'a> {
@@ -22,6 +75,41 @@ pub struct SelectionContext<'a> {
     #[allow(dead_code)]
     pub time_ms: u64,
     pub rng: &'a mut dyn RngCore,
+    pub session_id: Option<u64>,
+    pub request_key: Option<u64>,
+    pub tie_break: TieBreakConfig,
+}
+
+/// Seed of the stream strategies sample from under stable tie-breaks.
+pub const STABLE_SAMPLING_SEED: u64 = 0x5eed;
+
+/// Random source for strategies whose routing decision is itself random
+/// (not just their tie-breaks).
+///
+/// Under seeded tie-breaks draws come from `SelectionContext::rng`. Under
+/// stable tie-breaks that RNG always yields zero, which would pin every draw
+/// to index 0, so draws come instead from a `StdRng` seeded with
+/// `STABLE_SAMPLING_SEED` and owned by the strategy. Stable runs are
+/// therefore still deterministic and independent of `--seed`.
+pub struct SamplingRng {
+    stable: StdRng,
+}
+
+impl SamplingRng {
+    pub fn rng<'a>(&'a mut self, ctx: &'a mut SelectionContext) -> &'a mut dyn RngCore {
+        match ctx.tie_break {
+            TieBreakConfig::Stable => &mut self.stable,
+            TieBreakConfig::Seeded => &mut *ctx.rng,
+        }
+    }
+}
+
+impl Default for SamplingRng {
+    fn default() -> Self {
+        Self {
+            stable: StdRng::seed_from_u64(STABLE_SAMPLING_SEED),
+        }
+    }
 }
 
 pub struct Selection {
@@ -29,11 +117,66 @@ pub struct Selection {
     pub score: Option<u64>,
 }
 
-pub fn build_strategy(algo: AlgoConfig) -> Box<dyn SelectionStrategy> {
-    match algo {
-        AlgoConfig::RoundRobin => Box::new(RoundRobinStrategy::default()),
-        AlgoConfig::WeightedRoundRobin => Box::new(WeightedRoundRobinStrategy::default()),
-        AlgoConfig::LeastConnections => Box::new(LeastConnectionsStrategy::default()),
-        AlgoConfig::LeastResponseTime => Box::new(LeastResponseTimeStrategy::default()),
+/// Builds a balancer's strategy: `strategy` wrapped in the zone, tier and
+/// session layers `config` asks for.
+pub fn build_routing_strategy(
+    config: &SimConfig,
+    strategy: &StrategyRegistration,
+) -> Box<dyn SelectionStrategy> {
+    let routing = build_tiered_strategy(config, strategy);
+    match &config.sessions {
+        Some(sessions) => Box::new(StickyStrategy::new(routing, sessions.max_in_flight)),
+        None => routing,
+    }
+}
+
+fn build_tiered_strategy(
+    config: &SimConfig,
+    strategy: &StrategyRegistration,
+) -> Box<dyn SelectionStrategy> {
+    let tiered = config
+        .servers
+        .iter()
+        .any(|server| server.priority > 0 || !server.outages.is_empty());
+    if tiered || config.failover.is_some() {
+        let priorities = config
+            .servers
+            .iter()
+            .map(|server| server.priority)
+            .collect::<Vec<_>>();
+        let healthy_threshold = config
+            .failover
+            .clone()
+            .unwrap_or_default()
+            .healthy_threshold;
+        return Box::new(PriorityTierStrategy::new(
+            &priorities,
+            healthy_threshold,
+            || build_zone_strategy(config, strategy),
+        ));
+    }
+    build_zone_strategy(config, strategy)
+}
+
+fn build_zone_strategy(
+    config: &SimConfig,
+    strategy: &StrategyRegistration,
+) -> Box<dyn SelectionStrategy> {
+    match &config.locality {
+        Some(locality) => {
+            let is_local = config
+                .servers
+                .iter()
+                .map(|server| server.zone.as_ref() == Some(&locality.zone))
+                .collect();
+            Box::new(ZoneAwareStrategy::new(
+                is_local,
+                strategy.build(config),
+                strategy.build(config),
+                locality.spillover_threshold,
+                locality.spillover,
+            ))
+        }
+        None => strategy.build(config),
     }
 }
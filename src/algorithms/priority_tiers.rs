@@ -0,0 +1,223 @@
+use crate::algorithms::subset::ServerSubset;
+use crate::algorithms::{Selection, SelectionContext, SelectionStrategy};
+use crate::state::ServerState;
+
+/// Routes to the lowest priority tier whose healthy fraction is at least
+/// `healthy_threshold`, running a separate strategy inside each tier over
+/// that tier's healthy servers.
+///
+/// When no tier meets the threshold, the first tier with any healthy server
+/// is used. When every server is down the engine fails the request before
+/// selection; falling back to tier 0 regardless of health only keeps
+/// `select` total for a caller that does not.
+pub struct PriorityTierStrategy {
+    tier_of: Vec<usize>,
+    tiers: Vec<Tier>,
+    healthy_threshold: f64,
+}
+
+struct Tier {
+    subset: ServerSubset,
+    strategy: Box<dyn SelectionStrategy>,
+}
+
+impl PriorityTierStrategy {
+    /// `priorities` is indexed by `ServerId`; `build_strategy` is called once
+    /// per distinct priority.
+    pub fn new(
+        priorities: &[u32],
+        healthy_threshold: f64,
+        mut build_strategy: impl FnMut() -> Box<dyn SelectionStrategy>,
+    ) -> Self {
+        let mut levels = priorities.to_vec();
+        levels.sort_unstable();
+        levels.dedup();
+        let tier_of = priorities
+            .iter()
+            .map(|priority| levels.binary_search(priority).unwrap_or(0))
+            .collect();
+        let tiers = levels
+            .iter()
+            .map(|_| Tier {
+                subset: ServerSubset::default(),
+                strategy: build_strategy(),
+            })
+            .collect();
+
+        Self {
+            tier_of,
+            tiers,
+            healthy_threshold,
+        }
+    }
+
+    fn choose_tier(&self, servers: &[ServerState]) -> Option<usize> {
+        let mut totals = vec![(0usize, 0usize); self.tiers.len()];
+        for server in servers {
+            let tier = tier_of(&self.tier_of, server);
+            totals[tier].0 += 1;
+            if server.healthy {
+                totals[tier].1 += 1;
+            }
+        }
+
+        totals
+            .iter()
+            .position(|&(total, healthy)| {
+                total > 0 && healthy as f64 / total as f64 >= self.healthy_threshold
+            })
+            .or_else(|| totals.iter().position(|&(_, healthy)| healthy > 0))
+    }
+}
+
+impl SelectionStrategy for PriorityTierStrategy {
+    fn select(&mut self, ctx: &mut SelectionContext) -> Selection {
+        let tier_ids = &self.tier_of;
+        if let Some(tier) = self.choose_tier(ctx.servers) {
+            let selected = &mut self.tiers[tier];
+            if let Some(selection) =
+                selected
+                    .subset
+                    .select(selected.strategy.as_mut(), ctx, |server| {
+                        server.healthy && tier_of(tier_ids, server) == tier
+                    })
+            {
+                return selection;
+            }
+        }
+
+        let panic_tier = ctx
+            .servers
+            .iter()
+            .map(|server| tier_of(tier_ids, server))
+            .min()
+            .unwrap_or(0);
+        let selected = &mut self.tiers[panic_tier];
+        selected
+            .subset
+            .select(selected.strategy.as_mut(), ctx, |server| {
+                tier_of(tier_ids, server) == panic_tier
+            })
+            .expect("selection requires at least one server")
+    }
+
+    fn on_complete(&mut self, server: &ServerState) {
+        let tier = tier_of(&self.tier_of, server);
+        self.tiers[tier].strategy.on_complete(server);
+    }
+
+    fn take_error(&mut self) -> Option<String> {
+        self.tiers
+            .iter_mut()
+            .find_map(|tier| tier.strategy.take_error())
+    }
+}
+
+fn tier_of(tier_of: &[usize], server: &ServerState) -> usize {
+    tier_of.get(usize::from(server.id)).copied().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::RoundRobinStrategy;
+    use crate::models::TieBreakConfig;
+    use crate::state::ServerId;
+    use rand::SeedableRng;
+
+    fn server(id: usize, healthy: bool) -> ServerState {
+        ServerState {
+            id: ServerId::from(id),
+            name: format!("s{}", id),
+            base_latency_ms: 10,
+            weight: 1,
+            active_connections: 0,
+            pick_count: 0,
+            in_flight: 0,
+            next_available_ms: 0,
+            network_latency_ms: 0,
+            healthy,
+        }
+    }
+
+    fn tiered_strategy() -> PriorityTierStrategy {
+        PriorityTierStrategy::new(&[0, 0, 1, 1], 0.7, || {
+            Box::new(RoundRobinStrategy::default())
+        })
+    }
+
+    #[test]
+    fn priority_tiers_keep_traffic_on_healthy_primary() {
+        let servers = vec![
+            server(0, true),
+            server(1, true),
+            server(2, true),
+            server(3, true),
+        ];
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let mut strategy = tiered_strategy();
+        let mut ctx = SelectionContext {
+            servers: &servers,
+            time_ms: 0,
+            rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
+        };
+
+        let picks: Vec<ServerId> = (0..3)
+            .map(|_| strategy.select(&mut ctx).server_id)
+            .collect();
+        assert_eq!(
+            picks,
+            vec![ServerId::from(0), ServerId::from(1), ServerId::from(0)]
+        );
+    }
+
+    #[test]
+    fn priority_tiers_fail_over_below_healthy_threshold() {
+        let servers = vec![
+            server(0, false),
+            server(1, true),
+            server(2, true),
+            server(3, true),
+        ];
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let mut strategy = tiered_strategy();
+        let mut ctx = SelectionContext {
+            servers: &servers,
+            time_ms: 0,
+            rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
+        };
+
+        let picks: Vec<ServerId> = (0..2)
+            .map(|_| strategy.select(&mut ctx).server_id)
+            .collect();
+        assert_eq!(picks, vec![ServerId::from(2), ServerId::from(3)]);
+    }
+
+    #[test]
+    fn priority_tiers_panic_to_primary_when_all_down() {
+        let servers = vec![
+            server(0, false),
+            server(1, false),
+            server(2, false),
+            server(3, false),
+        ];
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let mut strategy = tiered_strategy();
+        let mut ctx = SelectionContext {
+            servers: &servers,
+            time_ms: 0,
+            rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
+        };
+
+        assert_eq!(strategy.select(&mut ctx).server_id, ServerId::from(0));
+    }
+}
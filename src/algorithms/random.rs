@@ -0,0 +1,98 @@
+use rand::Rng;
+
+use crate::algorithms::{SamplingRng, Selection, SelectionContext, SelectionStrategy};
+use crate::state::ServerId;
+
+/// Uniform random selection, the stateless baseline.
+#[derive(Default)]
+pub struct RandomStrategy {
+    sampling: SamplingRng,
+}
+
+impl SelectionStrategy for RandomStrategy {
+    fn select(&mut self, ctx: &mut SelectionContext) -> Selection {
+        let len = ctx.servers.len();
+        Selection {
+            server_id: ServerId::from(self.sampling.rng(ctx).gen_range(0..len)),
+            score: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::STABLE_SAMPLING_SEED;
+    use crate::models::TieBreakConfig;
+    use crate::state::ServerState;
+    use rand::rngs::mock::StepRng;
+    use rand::SeedableRng;
+
+    fn server(id: usize) -> ServerState {
+        ServerState {
+            id: ServerId::from(id),
+            name: format!("s{}", id),
+            base_latency_ms: 10,
+            weight: 1,
+            active_connections: 0,
+            pick_count: 0,
+            in_flight: 0,
+            next_available_ms: 0,
+            network_latency_ms: 0,
+            healthy: true,
+        }
+    }
+
+    #[test]
+    fn random_draws_from_context_rng_when_seeded() {
+        let servers: Vec<ServerState> = (0..4).map(server).collect();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(9);
+        let expected: Vec<usize> = (0..8).map(|_| rng.gen_range(0..servers.len())).collect();
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(9);
+        let mut strategy = RandomStrategy::default();
+        let picks: Vec<usize> = (0..8)
+            .map(|_| {
+                let mut ctx = SelectionContext {
+                    servers: &servers,
+                    time_ms: 0,
+                    rng: &mut rng,
+                    session_id: None,
+                    request_key: None,
+                    tie_break: TieBreakConfig::Seeded,
+                };
+                usize::from(strategy.select(&mut ctx).server_id)
+            })
+            .collect();
+
+        assert_eq!(picks, expected);
+    }
+
+    #[test]
+    fn random_uses_fixed_stream_under_stable_tiebreak() {
+        let servers: Vec<ServerState> = (0..4).map(server).collect();
+        let mut stream = rand::rngs::StdRng::seed_from_u64(STABLE_SAMPLING_SEED);
+        let expected: Vec<usize> = (0..32)
+            .map(|_| stream.gen_range(0..servers.len()))
+            .collect();
+
+        let mut rng = StepRng::new(0, 0);
+        let mut strategy = RandomStrategy::default();
+        let picks: Vec<usize> = (0..32)
+            .map(|_| {
+                let mut ctx = SelectionContext {
+                    servers: &servers,
+                    time_ms: 0,
+                    rng: &mut rng,
+                    session_id: None,
+                    request_key: None,
+                    tie_break: TieBreakConfig::Stable,
+                };
+                usize::from(strategy.select(&mut ctx).server_id)
+            })
+            .collect();
+
+        assert_eq!(picks, expected);
+        assert!(picks.iter().any(|&pick| pick != 0));
+    }
+}
@@ -0,0 +1,431 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::algorithms::{
+    ConsistentHashStrategy, JoinIdleQueueStrategy, JoinShortestQueueStrategy,
+    LeastConnectionsStrategy, LeastResponseTimeStrategy, RandomStrategy, RoundRobinStrategy,
+    SelectionStrategy, SmoothWeightedRoundRobinStrategy, WeightedLeastConnectionsStrategy,
+    WeightedLeastResponseTimeStrategy, WeightedRandomStrategy, WeightedRoundRobinStrategy,
+    DEFAULT_HASH_EPSILON, DEFAULT_JSQ_CHOICES, DEFAULT_VIRTUAL_NODES,
+};
+use crate::error::{Error, Result};
+use crate::models::{AlgoConfig, ParamValue, SimConfig};
+
+/// Builds a fresh strategy instance for a run. Called once per balancer, and
+/// once more per tier or zone the strategy is wrapped in.
+pub type StrategyConstructor = dyn Fn(&SimConfig) -> Box<dyn SelectionStrategy> + Send + Sync;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParamKind {
+    Integer,
+    Float,
+    Bool,
+    String,
+}
+
+impl fmt::Display for ParamKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            ParamKind::Integer => "integer",
+            ParamKind::Float => "float",
+            ParamKind::Bool => "bool",
+            ParamKind::String => "string",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+impl ParamKind {
+    fn accepts(&self, value: &ParamValue) -> bool {
+        matches!(
+            (self, value),
+            (ParamKind::Integer, ParamValue::Integer(_))
+                | (
+                    ParamKind::Float,
+                    ParamValue::Integer(_) | ParamValue::Float(_)
+                )
+                | (ParamKind::Bool, ParamValue::Bool(_))
+                | (ParamKind::String, ParamValue::String(_))
+        )
+    }
+}
+
+/// One tunable a strategy accepts, set through `AlgoConfig` params.
+#[derive(Clone, Debug)]
+pub struct ParamSpec {
+    pub name: String,
+    pub kind: ParamKind,
+    pub default: Option<String>,
+    /// Inclusive lower bound for integer and float values.
+    pub min: Option<f64>,
+    pub description: String,
+}
+
+impl ParamSpec {
+    pub fn new(name: impl Into<String>, kind: ParamKind, description: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            default: None,
+            min: None,
+            description: description.into(),
+        }
+    }
+
+    pub fn with_default(mut self, default: impl ToString) -> Self {
+        self.default = Some(default.to_string());
+        self
+    }
+
+    pub fn with_min(mut self, min: f64) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    fn check(&self, value: &ParamValue) -> std::result::Result<(), String> {
+        if !self.kind.accepts(value) {
+            return Err(format!(
+                "{} must be a {} (got {})",
+                self.name, self.kind, value
+            ));
+        }
+        if let (Some(min), Some(number)) = (self.min, value.as_f64()) {
+            if !(number.is_finite() && number >= min) {
+                return Err(format!("{} must be >= {} (got {})", self.name, min, value));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A strategy constructor registered under a name and optional short aliases.
+#[derive(Clone)]
+pub struct StrategyRegistration {
+    name: String,
+    aliases: Vec<String>,
+    description: String,
+    params: Vec<ParamSpec>,
+    constructor: Arc<StrategyConstructor>,
+}
+
+impl StrategyRegistration {
+    pub fn new(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        constructor: impl Fn(&SimConfig) -> Box<dyn SelectionStrategy> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            aliases: Vec::new(),
+            description: description.into(),
+            params: Vec::new(),
+            constructor: Arc::new(constructor),
+        }
+    }
+
+    pub fn with_param(mut self, param: ParamSpec) -> Self {
+        self.params.push(param);
+        self
+    }
+
+    /// Another name the strategy can be selected by, e.g. `rr`.
+    pub fn with_alias(mut self, alias: impl Into<String>) -> Self {
+        self.aliases.push(alias.into());
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+
+    /// Whether `name` is this strategy's name or one of its aliases.
+    pub fn answers_to(&self, name: &str) -> bool {
+        self.name == name || self.aliases.iter().any(|alias| alias == name)
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn params(&self) -> &[ParamSpec] {
+        &self.params
+    }
+
+    /// Checks `algo`'s params against the declared `ParamSpec`s.
+    pub fn validate_params(&self, algo: &AlgoConfig) -> Result<()> {
+        for (name, value) in algo.params() {
+            let spec = self
+                .params
+                .iter()
+                .find(|spec| &spec.name == name)
+                .ok_or_else(|| {
+                    let known: Vec<&str> =
+                        self.params.iter().map(|spec| spec.name.as_str()).collect();
+                    let expected = if known.is_empty() {
+                        "it takes no parameters".to_string()
+                    } else {
+                        format!("expected one of: {}", known.join(", "))
+                    };
+                    Error::InvalidAlgoParam(
+                        self.name.clone(),
+                        format!("unknown parameter '{}', {}", name, expected),
+                    )
+                })?;
+            spec.check(value)
+                .map_err(|message| Error::InvalidAlgoParam(self.name.clone(), message))?;
+        }
+        Ok(())
+    }
+
+    pub fn build(&self, config: &SimConfig) -> Box<dyn SelectionStrategy> {
+        (self.constructor)(config)
+    }
+}
+
+impl fmt::Debug for StrategyRegistration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StrategyRegistration")
+            .field("name", &self.name)
+            .field("aliases", &self.aliases)
+            .field("description", &self.description)
+            .field("params", &self.params)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Strategies available to `SimConfig::algo`, in registration order.
+#[derive(Clone, Debug, Default)]
+pub struct StrategyRegistry {
+    registrations: Vec<StrategyRegistration>,
+}
+
+impl StrategyRegistry {
+    /// An empty registry; see `builtin` for one holding lb-sim's strategies.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+        for registration in builtin_registrations() {
+            registry
+                .register(registration)
+                .expect("built-in strategy names are unique");
+        }
+        registry
+    }
+
+    pub fn register(&mut self, registration: StrategyRegistration) -> Result<()> {
+        let taken = std::iter::once(&registration.name)
+            .chain(&registration.aliases)
+            .find(|name| self.get(name).is_some());
+        if let Some(name) = taken {
+            return Err(Error::DuplicateAlgorithm(name.clone()));
+        }
+        self.registrations.push(registration);
+        Ok(())
+    }
+
+    /// Looks a strategy up by name or alias.
+    pub fn get(&self, name: &str) -> Option<&StrategyRegistration> {
+        self.registrations
+            .iter()
+            .find(|registration| registration.answers_to(name))
+    }
+
+    pub fn resolve(&self, name: &str) -> Result<&StrategyRegistration> {
+        self.get(name)
+            .ok_or_else(|| Error::UnknownAlgorithm(name.to_string()))
+    }
+
+    /// Resolves `algo` by name and validates its params.
+    pub fn resolve_algo(&self, algo: &AlgoConfig) -> Result<&StrategyRegistration> {
+        let registration = self.resolve(algo.name())?;
+        registration.validate_params(algo)?;
+        Ok(registration)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &StrategyRegistration> {
+        self.registrations.iter()
+    }
+}
+
+fn builtin_registrations() -> Vec<StrategyRegistration> {
+    vec![
+        StrategyRegistration::new("round-robin", "Cycles through backends in order.", |_| {
+            Box::new(RoundRobinStrategy::default())
+        })
+        .with_alias("rr"),
+        StrategyRegistration::new(
+            "weighted-round-robin",
+            "Round-robin in proportion to weight, sending each backend its share in a run.",
+            |_| Box::new(WeightedRoundRobinStrategy::default()),
+        )
+        .with_alias("wrr"),
+        StrategyRegistration::new(
+            "smooth-weighted-round-robin",
+            "nginx's smooth weighted round-robin: weighted proportions with interleaved picks.",
+            |_| Box::new(SmoothWeightedRoundRobinStrategy::default()),
+        )
+        .with_alias("swrr"),
+        StrategyRegistration::new(
+            "least-connections",
+            "Routes to the backend with the fewest active requests.",
+            |_| Box::new(LeastConnectionsStrategy::default()),
+        )
+        .with_alias("lc"),
+        StrategyRegistration::new(
+            "least-response-time",
+            "Routes to the backend with the earliest predicted completion.",
+            |_| Box::new(LeastResponseTimeStrategy::default()),
+        )
+        .with_alias("lrt"),
+        StrategyRegistration::new(
+            "weighted-least-connections",
+            "Routes to the backend with the fewest active requests per unit of weight.",
+            |_| Box::new(WeightedLeastConnectionsStrategy::default()),
+        )
+        .with_alias("wlc"),
+        StrategyRegistration::new(
+            "weighted-least-response-time",
+            "Routes by predicted completion delay divided by weight.",
+            |_| Box::new(WeightedLeastResponseTimeStrategy::default()),
+        )
+        .with_alias("wlrt"),
+        StrategyRegistration::new(
+            "join-idle-queue",
+            "Routes to a backend that announced itself idle, otherwise to a random one.",
+            |_| Box::new(JoinIdleQueueStrategy::default()),
+        )
+        .with_alias("jiq"),
+        StrategyRegistration::new(
+            "join-shortest-queue",
+            "Samples d backends and routes to the one with the shortest queue.",
+            |config| {
+                let choices = config
+                    .algo
+                    .param("d")
+                    .and_then(ParamValue::as_u64)
+                    .map_or(DEFAULT_JSQ_CHOICES, |d| d as usize);
+                Box::new(JoinShortestQueueStrategy::new(choices))
+            },
+        )
+        .with_alias("jsq")
+        .with_param(
+            ParamSpec::new("d", ParamKind::Integer, "Backends sampled per request")
+                .with_default(DEFAULT_JSQ_CHOICES)
+                .with_min(1.0),
+        ),
+        StrategyRegistration::new("random", "Picks a backend uniformly at random.", |_| {
+            Box::new(RandomStrategy::default())
+        }),
+        StrategyRegistration::new(
+            "weighted-random",
+            "Picks a backend at random in proportion to weight.",
+            |_| Box::new(WeightedRandomStrategy::default()),
+        )
+        .with_alias("wr"),
+        StrategyRegistration::new(
+            "consistent-hash",
+            "Consistent hashing on the request key with bounded loads.",
+            |config| {
+                let epsilon = config
+                    .algo
+                    .param("epsilon")
+                    .and_then(ParamValue::as_f64)
+                    .unwrap_or(DEFAULT_HASH_EPSILON);
+                let virtual_nodes = config
+                    .algo
+                    .param("virtual_nodes")
+                    .and_then(ParamValue::as_u64)
+                    .map_or(DEFAULT_VIRTUAL_NODES, |nodes| nodes as usize);
+                Box::new(ConsistentHashStrategy::new(epsilon, virtual_nodes))
+            },
+        )
+        .with_alias("ch")
+        .with_param(
+            ParamSpec::new(
+                "epsilon",
+                ParamKind::Float,
+                "Load bound: a backend takes at most (1+epsilon) times the average",
+            )
+            .with_default(DEFAULT_HASH_EPSILON)
+            .with_min(0.0),
+        )
+        .with_param(
+            ParamSpec::new(
+                "virtual_nodes",
+                ParamKind::Integer,
+                "Ring points per backend",
+            )
+            .with_default(DEFAULT_VIRTUAL_NODES)
+            .with_min(1.0),
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_registry_lists_strategies_in_order() {
+        let registry = StrategyRegistry::builtin();
+        let names: Vec<&str> = registry.iter().map(StrategyRegistration::name).collect();
+
+        assert_eq!(names.first(), Some(&"round-robin"));
+        assert_eq!(names.len(), 12);
+        assert!(registry.get("least-connections").is_some());
+        assert_eq!(
+            registry.get("lrt").map(StrategyRegistration::name),
+            Some("least-response-time")
+        );
+        assert!(matches!(
+            registry.resolve("fastest"),
+            Err(Error::UnknownAlgorithm(name)) if name == "fastest"
+        ));
+    }
+
+    #[test]
+    fn register_rejects_duplicate_names() {
+        let mut registry = StrategyRegistry::builtin();
+        let duplicate =
+            StrategyRegistration::new("random", "again", |_| Box::new(RandomStrategy::default()));
+
+        assert!(matches!(
+            registry.register(duplicate),
+            Err(Error::DuplicateAlgorithm(name)) if name == "random"
+        ));
+        let alias_clash =
+            StrategyRegistration::new("rr", "again", |_| Box::new(RandomStrategy::default()));
+        assert!(matches!(
+            registry.register(alias_clash),
+            Err(Error::DuplicateAlgorithm(name)) if name == "rr"
+        ));
+    }
+
+    #[test]
+    fn validate_params_checks_names_kinds_and_bounds() {
+        let registry = StrategyRegistry::builtin();
+        let check = |algo: AlgoConfig| registry.resolve_algo(&algo).map(|_| ());
+
+        assert!(check(AlgoConfig::new("join-shortest-queue").with_param("d", 3)).is_ok());
+        assert!(check(AlgoConfig::new("consistent-hash").with_param("epsilon", 1)).is_ok());
+        for algo in [
+            AlgoConfig::new("round-robin").with_param("d", 3),
+            AlgoConfig::new("join-shortest-queue").with_param("d", 2.5),
+            AlgoConfig::new("join-shortest-queue").with_param("d", 0),
+            AlgoConfig::new("consistent-hash").with_param("epsilon", "wide"),
+        ] {
+            assert!(
+                matches!(check(algo.clone()), Err(Error::InvalidAlgoParam(..))),
+                "{} should be rejected",
+                algo
+            );
+        }
+    }
+}
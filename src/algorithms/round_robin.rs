@@ -20,6 +20,7 @@ impl SelectionStrategy for RoundRobinStrategy {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::TieBreakConfig;
     use crate::state::{ServerId, ServerState};
     use rand::SeedableRng;
 
@@ -35,6 +36,8 @@ mod tests {
                 pick_count: 0,
                 in_flight: 0,
                 next_available_ms: 0,
+                network_latency_ms: 0,
+                healthy: true,
             },
             ServerState {
                 id: ServerId::from(1),
@@ -45,6 +48,8 @@ mod tests {
                 pick_count: 0,
                 in_flight: 0,
                 next_available_ms: 0,
+                network_latency_ms: 0,
+                healthy: true,
             },
             ServerState {
                 id: ServerId::from(2),
@@ -55,6 +60,8 @@ mod tests {
                 pick_count: 0,
                 in_flight: 0,
                 next_available_ms: 0,
+                network_latency_ms: 0,
+                healthy: true,
             },
         ];
         let mut rng = rand::rngs::StdRng::seed_from_u64(1);
@@ -63,6 +70,9 @@ mod tests {
             servers: &servers,
             time_ms: 0,
             rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
         };
 
         assert_eq!(strategy.select(&mut ctx).server_id, ServerId::from(0));
@@ -0,0 +1,250 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use rhai::{Array, Dynamic, Engine, Map, Scope, AST, INT};
+
+use crate::algorithms::{Selection, SelectionContext, SelectionStrategy, StrategyRegistration};
+use crate::error::{Error, Result};
+use crate::state::{ServerId, ServerState};
+
+/// Operations a script may run per selection before it is stopped, so a
+/// script stuck in a loop fails the run instead of hanging it.
+pub const SCRIPT_MAX_OPERATIONS: u64 = 1_000_000;
+
+/// A selection strategy written in Rhai.
+///
+/// The script defines `fn select(servers, time_ms)`. `servers` is an array of
+/// maps holding a copy of each `ServerState` field (`id`, `name`,
+/// `base_latency_ms`, `weight`, `active_connections`, `pick_count`,
+/// `in_flight`, `next_available_ms`, `network_latency_ms`, `healthy`). It
+/// returns the index of the chosen server, or `#{ index: i, score: s }` to
+/// also report a score.
+///
+/// ```rhai
+/// fn select(servers, time_ms) {
+///     let best = 0;
+///     for i in 1..servers.len() {
+///         if servers[i].in_flight < servers[best].in_flight { best = i; }
+///     }
+///     best
+/// }
+/// ```
+pub struct ScriptStrategy {
+    engine: Engine,
+    ast: Arc<AST>,
+    error: Option<String>,
+}
+
+impl ScriptStrategy {
+    pub fn from_source(source: &str) -> Result<Self> {
+        let ast = compile(source).map_err(Error::Script)?;
+        Ok(Self::from_ast(Arc::new(ast)))
+    }
+
+    /// Registers the script at `path` under `name`. The script is compiled
+    /// once here; each strategy instance gets its own interpreter.
+    pub fn registration(name: &str, path: &Path) -> Result<StrategyRegistration> {
+        let source = std::fs::read_to_string(path)
+            .map_err(|err| Error::Script(format!("{}: {}", path.display(), err)))?;
+        let ast = compile(&source)
+            .map_err(|err| Error::Script(format!("{}: {}", path.display(), err)))?;
+        let ast = Arc::new(ast);
+        Ok(StrategyRegistration::new(
+            name,
+            format!("Rhai script {}.", path.display()),
+            move |_| Box::new(ScriptStrategy::from_ast(Arc::clone(&ast))),
+        ))
+    }
+
+    fn from_ast(ast: Arc<AST>) -> Self {
+        let mut engine = Engine::new();
+        engine.set_max_operations(SCRIPT_MAX_OPERATIONS);
+        Self {
+            engine,
+            ast,
+            error: None,
+        }
+    }
+
+    fn call(&self, ctx: &SelectionContext) -> std::result::Result<Selection, String> {
+        let servers: Array = ctx.servers.iter().map(server_map).collect();
+        let returned: Dynamic = self
+            .engine
+            .call_fn(
+                &mut Scope::new(),
+                &self.ast,
+                "select",
+                (servers, ctx.time_ms as INT),
+            )
+            .map_err(|err| err.to_string())?;
+
+        let (index, score) = match returned.clone().try_cast::<Map>() {
+            Some(map) => (
+                map.get("index").cloned().unwrap_or(Dynamic::UNIT),
+                map.get("score").cloned().unwrap_or(Dynamic::UNIT),
+            ),
+            None => (returned, Dynamic::UNIT),
+        };
+        let index = index
+            .as_int()
+            .ok()
+            .and_then(|index| usize::try_from(index).ok())
+            .filter(|&index| index < ctx.servers.len())
+            .ok_or_else(|| {
+                format!(
+                    "select must return a server index below {} (got {})",
+                    ctx.servers.len(),
+                    index
+                )
+            })?;
+        let score = if score.is_unit() {
+            None
+        } else {
+            let value = score
+                .as_int()
+                .ok()
+                .and_then(|score| u64::try_from(score).ok())
+                .ok_or_else(|| format!("score must be a non-negative integer (got {})", score))?;
+            Some(value)
+        };
+
+        Ok(Selection {
+            server_id: ServerId::from(index),
+            score,
+        })
+    }
+}
+
+impl SelectionStrategy for ScriptStrategy {
+    fn select(&mut self, ctx: &mut SelectionContext) -> Selection {
+        match self.call(ctx) {
+            Ok(selection) => selection,
+            Err(message) => {
+                self.error.get_or_insert(message);
+                Selection {
+                    server_id: ServerId::from(0),
+                    score: None,
+                }
+            }
+        }
+    }
+
+    fn take_error(&mut self) -> Option<String> {
+        self.error.take()
+    }
+}
+
+fn compile(source: &str) -> std::result::Result<AST, String> {
+    let ast = Engine::new()
+        .compile(source)
+        .map_err(|err| err.to_string())?;
+    if !ast
+        .iter_functions()
+        .any(|function| function.name == "select" && function.params.len() == 2)
+    {
+        return Err("script must define fn select(servers, time_ms)".to_string());
+    }
+    Ok(ast)
+}
+
+fn server_map(server: &ServerState) -> Dynamic {
+    let mut map = Map::new();
+    map.insert("id".into(), (usize::from(server.id) as INT).into());
+    map.insert("name".into(), server.name.clone().into());
+    map.insert(
+        "base_latency_ms".into(),
+        (server.base_latency_ms as INT).into(),
+    );
+    map.insert("weight".into(), INT::from(server.weight).into());
+    map.insert(
+        "active_connections".into(),
+        INT::from(server.active_connections).into(),
+    );
+    map.insert("pick_count".into(), INT::from(server.pick_count).into());
+    map.insert("in_flight".into(), INT::from(server.in_flight).into());
+    map.insert(
+        "next_available_ms".into(),
+        (server.next_available_ms as INT).into(),
+    );
+    map.insert(
+        "network_latency_ms".into(),
+        (server.network_latency_ms as INT).into(),
+    );
+    map.insert("healthy".into(), server.healthy.into());
+    map.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TieBreakConfig;
+
+    fn server(id: usize, in_flight: u32) -> ServerState {
+        ServerState {
+            id: ServerId::from(id),
+            name: format!("s{}", id),
+            base_latency_ms: 10,
+            weight: 1,
+            active_connections: in_flight,
+            pick_count: 0,
+            in_flight,
+            next_available_ms: 0,
+            network_latency_ms: 0,
+            healthy: true,
+        }
+    }
+
+    fn select(strategy: &mut ScriptStrategy, servers: &[ServerState]) -> Selection {
+        let mut rng = rand::rngs::mock::StepRng::new(0, 0);
+        let mut ctx = SelectionContext {
+            servers,
+            time_ms: 5,
+            rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Stable,
+        };
+        strategy.select(&mut ctx)
+    }
+
+    #[test]
+    fn script_selects_server_with_score() {
+        let mut strategy = ScriptStrategy::from_source(
+            r#"
+            fn select(servers, time_ms) {
+                let best = 0;
+                for i in 1..servers.len() {
+                    if servers[i].in_flight < servers[best].in_flight { best = i; }
+                }
+                #{ index: best, score: servers[best].in_flight + time_ms }
+            }
+            "#,
+        )
+        .expect("script should compile");
+        let servers = [server(0, 3), server(1, 1), server(2, 2)];
+
+        let selection = select(&mut strategy, &servers);
+
+        assert_eq!(selection.server_id, ServerId::from(1));
+        assert_eq!(selection.score, Some(6));
+        assert_eq!(strategy.take_error(), None);
+    }
+
+    #[test]
+    fn script_errors_are_reported() {
+        let servers = [server(0, 0), server(1, 0)];
+        for source in [
+            "fn select(servers, time_ms) { servers.len() }",
+            "fn select(servers, time_ms) { loop {} }",
+        ] {
+            let mut strategy = ScriptStrategy::from_source(source).expect("script should compile");
+            assert_eq!(select(&mut strategy, &servers).server_id, ServerId::from(0));
+            assert!(strategy.take_error().is_some(), "{}", source);
+        }
+
+        assert!(matches!(
+            ScriptStrategy::from_source("fn choose(servers) { 0 }"),
+            Err(Error::Script(_))
+        ));
+    }
+}
@@ -0,0 +1,133 @@
+use crate::algorithms::{Selection, SelectionContext, SelectionStrategy};
+use crate::state::ServerId;
+
+/// nginx's smooth weighted round-robin: every pick adds each server's weight
+/// to its current weight, selects the highest current weight, and subtracts
+/// the total weight from the winner. Picks are interleaved instead of sent in
+/// runs, and weights are read on every pick so runtime changes apply at once.
+#[derive(Default)]
+pub struct SmoothWeightedRoundRobinStrategy {
+    current_weights: Vec<i64>,
+}
+
+impl SelectionStrategy for SmoothWeightedRoundRobinStrategy {
+    fn select(&mut self, ctx: &mut SelectionContext) -> Selection {
+        if self.current_weights.len() != ctx.servers.len() {
+            self.current_weights.clear();
+            self.current_weights.resize(ctx.servers.len(), 0);
+        }
+
+        let mut total_weight = 0i64;
+        let mut selected = 0usize;
+        for (idx, server) in ctx.servers.iter().enumerate() {
+            let weight = i64::from(server.weight);
+            total_weight += weight;
+            self.current_weights[idx] += weight;
+            if self.current_weights[idx] > self.current_weights[selected] {
+                selected = idx;
+            }
+        }
+        self.current_weights[selected] -= total_weight;
+
+        Selection {
+            server_id: ServerId::from(selected),
+            score: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::WeightedRoundRobinStrategy;
+    use crate::models::TieBreakConfig;
+    use crate::state::{ServerId, ServerState};
+    use rand::SeedableRng;
+
+    fn weighted_servers(weights: &[u32]) -> Vec<ServerState> {
+        weights
+            .iter()
+            .enumerate()
+            .map(|(idx, &weight)| ServerState {
+                id: ServerId::from(idx),
+                name: format!("s{}", idx),
+                base_latency_ms: 10,
+                weight,
+                active_connections: 0,
+                pick_count: 0,
+                in_flight: 0,
+                next_available_ms: 0,
+                network_latency_ms: 0,
+                healthy: true,
+            })
+            .collect()
+    }
+
+    fn picks(
+        strategy: &mut dyn SelectionStrategy,
+        servers: &[ServerState],
+        count: usize,
+    ) -> Vec<usize> {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let mut ctx = SelectionContext {
+            servers,
+            time_ms: 0,
+            rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
+        };
+        (0..count)
+            .map(|_| usize::from(strategy.select(&mut ctx).server_id))
+            .collect()
+    }
+
+    fn longest_run(picks: &[usize]) -> usize {
+        let mut longest = 0;
+        let mut current = 0;
+        for (idx, pick) in picks.iter().enumerate() {
+            if idx > 0 && picks[idx - 1] == *pick {
+                current += 1;
+            } else {
+                current = 1;
+            }
+            longest = longest.max(current);
+        }
+        longest
+    }
+
+    #[test]
+    fn smooth_weighted_round_robin_interleaves_picks() {
+        let servers = weighted_servers(&[5, 1, 1]);
+        let mut strategy = SmoothWeightedRoundRobinStrategy::default();
+
+        assert_eq!(picks(&mut strategy, &servers, 7), vec![0, 0, 1, 0, 2, 0, 0]);
+    }
+
+    #[test]
+    fn smooth_and_prefix_weighted_round_robin_share_proportions_not_burstiness() {
+        let servers = weighted_servers(&[5, 2]);
+        let smooth = picks(
+            &mut SmoothWeightedRoundRobinStrategy::default(),
+            &servers,
+            700,
+        );
+        let prefix = picks(&mut WeightedRoundRobinStrategy::default(), &servers, 700);
+
+        let share = |picks: &[usize]| picks.iter().filter(|&&pick| pick == 0).count();
+        assert_eq!(share(&smooth), 500);
+        assert_eq!(share(&prefix), 500);
+        assert_eq!(longest_run(&prefix), 5);
+        assert_eq!(longest_run(&smooth), 3);
+    }
+
+    #[test]
+    fn smooth_weighted_round_robin_follows_runtime_weight_changes() {
+        let mut strategy = SmoothWeightedRoundRobinStrategy::default();
+        picks(&mut strategy, &weighted_servers(&[1, 1]), 2);
+
+        let reweighted = weighted_servers(&[1, 3]);
+        let after = picks(&mut strategy, &reweighted, 8);
+        assert_eq!(after.iter().filter(|&&pick| pick == 1).count(), 6);
+    }
+}
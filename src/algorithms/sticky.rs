@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+
+use crate::algorithms::{Selection, SelectionContext, SelectionStrategy};
+use crate::state::{ServerId, ServerState};
+
+/// Pins each session to the server that handled its first request.
+///
+/// Requests without a session, first requests of a session, and requests
+/// whose pinned server is down or at `max_in_flight` are balanced by the
+/// inner strategy, which then re-pins the session.
+pub struct StickyStrategy {
+    inner: Box<dyn SelectionStrategy>,
+    max_in_flight: Option<u32>,
+    pinned: HashMap<u64, ServerId>,
+}
+
+impl StickyStrategy {
+    pub fn new(inner: Box<dyn SelectionStrategy>, max_in_flight: Option<u32>) -> Self {
+        Self {
+            inner,
+            max_in_flight,
+            pinned: HashMap::new(),
+        }
+    }
+}
+
+impl SelectionStrategy for StickyStrategy {
+    fn select(&mut self, ctx: &mut SelectionContext) -> Selection {
+        let Some(session_id) = ctx.session_id else {
+            return self.inner.select(ctx);
+        };
+
+        if let Some(pinned) = self.pinned.get(&session_id) {
+            let available = ctx.servers.iter().position(|server| {
+                server.id == *pinned
+                    && server.healthy
+                    && self
+                        .max_in_flight
+                        .is_none_or(|limit| server.in_flight < limit)
+            });
+            if let Some(position) = available {
+                return Selection {
+                    server_id: ServerId::from(position),
+                    score: None,
+                };
+            }
+        }
+
+        let selection = self.inner.select(ctx);
+        let server_id = ctx.servers[usize::from(selection.server_id)].id;
+        self.pinned.insert(session_id, server_id);
+        selection
+    }
+
+    fn on_complete(&mut self, server: &ServerState) {
+        self.inner.on_complete(server);
+    }
+
+    fn on_session_end(&mut self, session_id: u64) {
+        self.pinned.remove(&session_id);
+        self.inner.on_session_end(session_id);
+    }
+
+    fn take_error(&mut self) -> Option<String> {
+        self.inner.take_error()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::RoundRobinStrategy;
+    use crate::models::TieBreakConfig;
+    use rand::SeedableRng;
+
+    fn server(id: usize, in_flight: u32) -> ServerState {
+        ServerState {
+            id: ServerId::from(id),
+            name: format!("s{}", id),
+            base_latency_ms: 10,
+            weight: 1,
+            active_connections: in_flight,
+            pick_count: 0,
+            in_flight,
+            next_available_ms: 0,
+            network_latency_ms: 0,
+            healthy: true,
+        }
+    }
+
+    #[test]
+    fn sticky_keeps_sessions_on_first_server() {
+        let servers = vec![server(0, 0), server(1, 0)];
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let mut strategy = StickyStrategy::new(Box::new(RoundRobinStrategy::default()), None);
+
+        let picks: Vec<ServerId> = [Some(7), Some(8), Some(7), None, Some(7)]
+            .into_iter()
+            .map(|session_id| {
+                let mut ctx = SelectionContext {
+                    servers: &servers,
+                    time_ms: 0,
+                    rng: &mut rng,
+                    session_id,
+                    request_key: None,
+                    tie_break: TieBreakConfig::Seeded,
+                };
+                strategy.select(&mut ctx).server_id
+            })
+            .collect();
+        assert_eq!(
+            picks,
+            vec![
+                ServerId::from(0),
+                ServerId::from(1),
+                ServerId::from(0),
+                ServerId::from(0),
+                ServerId::from(0),
+            ]
+        );
+    }
+
+    #[test]
+    fn sticky_breaks_when_pinned_server_is_overloaded() {
+        let idle = vec![server(0, 0), server(1, 0)];
+        let overloaded = vec![server(0, 4), server(1, 0)];
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let mut strategy = StickyStrategy::new(Box::new(RoundRobinStrategy::default()), Some(4));
+
+        let mut ctx = SelectionContext {
+            servers: &idle,
+            time_ms: 0,
+            rng: &mut rng,
+            session_id: Some(1),
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
+        };
+        assert_eq!(strategy.select(&mut ctx).server_id, ServerId::from(0));
+
+        let mut ctx = SelectionContext {
+            servers: &overloaded,
+            time_ms: 0,
+            rng: &mut rng,
+            session_id: Some(1),
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
+        };
+        assert_eq!(strategy.select(&mut ctx).server_id, ServerId::from(1));
+    }
+
+    #[test]
+    fn sticky_drops_pin_when_session_ends() {
+        let servers = vec![server(0, 0), server(1, 0)];
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let mut strategy = StickyStrategy::new(Box::new(RoundRobinStrategy::default()), None);
+
+        let mut ctx = SelectionContext {
+            servers: &servers,
+            time_ms: 0,
+            rng: &mut rng,
+            session_id: Some(1),
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
+        };
+        strategy.select(&mut ctx);
+        assert_eq!(strategy.pinned.len(), 1);
+
+        strategy.on_session_end(1);
+        assert!(strategy.pinned.is_empty());
+    }
+}
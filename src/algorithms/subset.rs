@@ -0,0 +1,60 @@
+use crate::algorithms::{Selection, SelectionContext, SelectionStrategy};
+use crate::state::{ServerId, ServerState};
+
+/// Runs a strategy against the servers that match a filter and maps its
+/// selection back to a position in the caller's server list.
+///
+/// Membership is decided per server (usually by `ServerState::id`), so
+/// subsets nest: a strategy running inside a subset can use its own subset.
+#[derive(Default)]
+pub struct ServerSubset {
+    positions: Vec<usize>,
+    view: Vec<ServerState>,
+}
+
+impl ServerSubset {
+    pub fn select(
+        &mut self,
+        strategy: &mut dyn SelectionStrategy,
+        ctx: &mut SelectionContext,
+        filter: impl Fn(&ServerState) -> bool,
+    ) -> Option<Selection> {
+        self.positions.clear();
+        self.positions.extend(
+            ctx.servers
+                .iter()
+                .enumerate()
+                .filter(|(_, server)| filter(server))
+                .map(|(idx, _)| idx),
+        );
+        if self.positions.is_empty() {
+            return None;
+        }
+
+        if self.view.len() != self.positions.len() {
+            self.view = self
+                .positions
+                .iter()
+                .map(|&idx| ctx.servers[idx].clone())
+                .collect();
+        } else {
+            for (slot, &idx) in self.view.iter_mut().zip(&self.positions) {
+                slot.clone_from(&ctx.servers[idx]);
+            }
+        }
+
+        let mut subset_ctx = SelectionContext {
+            servers: &self.view,
+            time_ms: ctx.time_ms,
+            rng: &mut *ctx.rng,
+            session_id: ctx.session_id,
+            request_key: ctx.request_key,
+            tie_break: ctx.tie_break,
+        };
+        let selection = strategy.select(&mut subset_ctx);
+        Some(Selection {
+            server_id: ServerId::from(self.positions[usize::from(selection.server_id)]),
+            score: selection.score,
+        })
+    }
+}
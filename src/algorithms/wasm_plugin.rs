@@ -0,0 +1,380 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use wasmi::{Config, Engine, Instance, Linker, Memory, Module, Store, TypedFunc};
+
+use crate::algorithms::{Selection, SelectionContext, SelectionStrategy, StrategyRegistration};
+use crate::error::{Error, Result};
+use crate::state::{ServerId, ServerState};
+
+/// Version of the plugin ABI described on `WasmStrategy`.
+pub const PLUGIN_ABI_VERSION: i32 = 1;
+
+/// Fuel (roughly, WebAssembly instructions) a plugin may spend per selection.
+pub const PLUGIN_FUEL_PER_SELECT: u64 = 10_000_000;
+
+/// Bytes per server in the snapshot passed to `select`.
+pub const PLUGIN_SERVER_RECORD_SIZE: usize = 48;
+
+/// A selection strategy loaded from a WebAssembly module and run in wasmi
+/// with a fuel limit per selection.
+///
+/// ABI version 1. The module imports nothing and exports:
+///
+/// * `memory`
+/// * `lb_abi_version() -> i32`, returning 1
+/// * `alloc(len: i32) -> i32`, returning a buffer of at least `len` bytes
+///   that stays valid until the next `alloc` call; the host reuses it across
+///   selections
+/// * `select(ptr: i32, count: i32, time_ms: i64) -> i64`, returning the index
+///   of the chosen server, or a negative value to report an error
+/// * optionally `score() -> i64`, called after `select`; negative means none
+///
+/// The snapshot at `ptr` holds `count` little-endian records of
+/// `PLUGIN_SERVER_RECORD_SIZE` bytes, one per server in selection order:
+///
+/// | offset | type | field                |
+/// | -----: | ---- | -------------------- |
+/// |      0 | u32  | `id`                 |
+/// |      4 | u32  | `weight`             |
+/// |      8 | u32  | `active_connections` |
+/// |     12 | u32  | `in_flight`          |
+/// |     16 | u32  | `pick_count`         |
+/// |     20 | u32  | `healthy` (0 or 1)   |
+/// |     24 | u64  | `base_latency_ms`    |
+/// |     32 | u64  | `next_available_ms`  |
+/// |     40 | u64  | `network_latency_ms` |
+pub struct WasmStrategy {
+    module: Arc<Module>,
+    plugin: Option<Plugin>,
+    snapshot: Vec<u8>,
+    error: Option<String>,
+}
+
+struct Plugin {
+    store: Store<()>,
+    memory: Memory,
+    alloc: TypedFunc<i32, i32>,
+    select: TypedFunc<(i32, i32, i64), i64>,
+    score: Option<TypedFunc<(), i64>>,
+    buffer: Option<(i32, usize)>,
+}
+
+impl WasmStrategy {
+    pub fn from_bytes(wasm: &[u8]) -> Result<Self> {
+        let module = compile(wasm).map_err(Error::Plugin)?;
+        Ok(Self::from_module(Arc::new(module)))
+    }
+
+    /// Registers the module at `path` under `name`. The module is compiled
+    /// and checked once here; each strategy instance gets its own store.
+    pub fn registration(name: &str, path: &Path) -> Result<StrategyRegistration> {
+        let wasm = std::fs::read(path)
+            .map_err(|err| Error::Plugin(format!("{}: {}", path.display(), err)))?;
+        let module =
+            compile(&wasm).map_err(|err| Error::Plugin(format!("{}: {}", path.display(), err)))?;
+        let module = Arc::new(module);
+        Ok(StrategyRegistration::new(
+            name,
+            format!("WebAssembly plugin {}.", path.display()),
+            move |_| Box::new(WasmStrategy::from_module(Arc::clone(&module))),
+        ))
+    }
+
+    fn from_module(module: Arc<Module>) -> Self {
+        Self {
+            module,
+            plugin: None,
+            snapshot: Vec::new(),
+            error: None,
+        }
+    }
+
+    fn call(&mut self, ctx: &SelectionContext) -> std::result::Result<Selection, String> {
+        if self.plugin.is_none() {
+            self.plugin = Some(Plugin::instantiate(&self.module)?);
+        }
+        let plugin = self.plugin.as_mut().expect("plugin was just instantiated");
+
+        self.snapshot.clear();
+        self.snapshot
+            .reserve(ctx.servers.len() * PLUGIN_SERVER_RECORD_SIZE);
+        for server in ctx.servers {
+            write_record(&mut self.snapshot, server);
+        }
+        let (ptr, count) = plugin.write_snapshot(&self.snapshot, ctx.servers.len())?;
+
+        plugin
+            .store
+            .set_fuel(PLUGIN_FUEL_PER_SELECT)
+            .map_err(|err| err.to_string())?;
+        let returned = plugin
+            .select
+            .call(&mut plugin.store, (ptr, count, ctx.time_ms as i64))
+            .map_err(|err| err.to_string())?;
+        let index = usize::try_from(returned)
+            .ok()
+            .filter(|&index| index < ctx.servers.len())
+            .ok_or_else(|| {
+                format!(
+                    "select must return a server index below {} (got {})",
+                    ctx.servers.len(),
+                    returned
+                )
+            })?;
+        let score = match &plugin.score {
+            Some(score) => {
+                let value = score
+                    .call(&mut plugin.store, ())
+                    .map_err(|err| err.to_string())?;
+                u64::try_from(value).ok()
+            }
+            None => None,
+        };
+
+        Ok(Selection {
+            server_id: ServerId::from(index),
+            score,
+        })
+    }
+}
+
+impl SelectionStrategy for WasmStrategy {
+    fn select(&mut self, ctx: &mut SelectionContext) -> Selection {
+        match self.call(ctx) {
+            Ok(selection) => selection,
+            Err(message) => {
+                self.error.get_or_insert(message);
+                Selection {
+                    server_id: ServerId::from(0),
+                    score: None,
+                }
+            }
+        }
+    }
+
+    fn take_error(&mut self) -> Option<String> {
+        self.error.take()
+    }
+}
+
+impl Plugin {
+    fn instantiate(module: &Module) -> std::result::Result<Self, String> {
+        let mut store = Store::new(module.engine(), ());
+        store
+            .set_fuel(PLUGIN_FUEL_PER_SELECT)
+            .map_err(|err| err.to_string())?;
+        let instance = Linker::<()>::new(module.engine())
+            .instantiate(&mut store, module)
+            .and_then(|pre| pre.start(&mut store))
+            .map_err(|err| err.to_string())?;
+
+        let version = typed_func::<(), i32>(&instance, &store, "lb_abi_version")?
+            .call(&mut store, ())
+            .map_err(|err| err.to_string())?;
+        if version != PLUGIN_ABI_VERSION {
+            return Err(format!(
+                "plugin ABI version {} is not supported (expected {})",
+                version, PLUGIN_ABI_VERSION
+            ));
+        }
+        let memory = instance
+            .get_memory(&store, "memory")
+            .ok_or_else(|| "plugin must export memory".to_string())?;
+        let alloc = typed_func(&instance, &store, "alloc")?;
+        let select = typed_func(&instance, &store, "select")?;
+        let score = match instance.get_export(&store, "score") {
+            Some(_) => Some(typed_func(&instance, &store, "score")?),
+            None => None,
+        };
+
+        Ok(Self {
+            store,
+            memory,
+            alloc,
+            select,
+            score,
+            buffer: None,
+        })
+    }
+
+    /// Copies `snapshot` into plugin memory, asking the plugin for a larger
+    /// buffer when the current one is too small.
+    fn write_snapshot(
+        &mut self,
+        snapshot: &[u8],
+        count: usize,
+    ) -> std::result::Result<(i32, i32), String> {
+        let ptr = match self.buffer {
+            Some((ptr, capacity)) if capacity >= snapshot.len() => ptr,
+            _ => {
+                let len = i32::try_from(snapshot.len()).map_err(|err| err.to_string())?;
+                self.store
+                    .set_fuel(PLUGIN_FUEL_PER_SELECT)
+                    .map_err(|err| err.to_string())?;
+                let ptr = self
+                    .alloc
+                    .call(&mut self.store, len)
+                    .map_err(|err| err.to_string())?;
+                self.buffer = Some((ptr, snapshot.len()));
+                ptr
+            }
+        };
+        let offset = usize::try_from(ptr).map_err(|_| format!("alloc returned {}", ptr))?;
+        self.memory
+            .write(&mut self.store, offset, snapshot)
+            .map_err(|err| format!("alloc returned an invalid buffer: {}", err))?;
+        let count = i32::try_from(count).map_err(|err| err.to_string())?;
+        Ok((ptr, count))
+    }
+}
+
+fn compile(wasm: &[u8]) -> std::result::Result<Module, String> {
+    let mut config = Config::default();
+    config.consume_fuel(true);
+    let engine = Engine::new(&config);
+    let module = Module::new(&engine, wasm).map_err(|err| err.to_string())?;
+    if module.imports().len() > 0 {
+        return Err("plugins must not import anything".to_string());
+    }
+    Plugin::instantiate(&module)?;
+    Ok(module)
+}
+
+fn typed_func<Params, Results>(
+    instance: &Instance,
+    store: &Store<()>,
+    name: &str,
+) -> std::result::Result<TypedFunc<Params, Results>, String>
+where
+    Params: wasmi::WasmParams,
+    Results: wasmi::WasmResults,
+{
+    instance
+        .get_typed_func(store, name)
+        .map_err(|err| format!("plugin export '{}': {}", name, err))
+}
+
+fn write_record(snapshot: &mut Vec<u8>, server: &ServerState) {
+    let id = u32::try_from(usize::from(server.id)).unwrap_or(u32::MAX);
+    snapshot.extend_from_slice(&id.to_le_bytes());
+    snapshot.extend_from_slice(&server.weight.to_le_bytes());
+    snapshot.extend_from_slice(&server.active_connections.to_le_bytes());
+    snapshot.extend_from_slice(&server.in_flight.to_le_bytes());
+    snapshot.extend_from_slice(&server.pick_count.to_le_bytes());
+    snapshot.extend_from_slice(&u32::from(server.healthy).to_le_bytes());
+    snapshot.extend_from_slice(&server.base_latency_ms.to_le_bytes());
+    snapshot.extend_from_slice(&server.next_available_ms.to_le_bytes());
+    snapshot.extend_from_slice(&server.network_latency_ms.to_le_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TieBreakConfig;
+
+    /// Picks the server with the fewest requests in flight and reports that
+    /// count as its score.
+    const LEAST_IN_FLIGHT: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (global $score (mut i64) (i64.const -1))
+          (func (export "lb_abi_version") (result i32) (i32.const 1))
+          (func (export "alloc") (param i32) (result i32) (i32.const 1024))
+          (func (export "score") (result i64) (global.get $score))
+          (func (export "select") (param $ptr i32) (param $count i32) (param $time i64) (result i64)
+            (local $i i32) (local $best i32) (local $min i32) (local $v i32)
+            (local.set $min (i32.const -1))
+            (block $done
+              (loop $next
+                (br_if $done (i32.ge_u (local.get $i) (local.get $count)))
+                (local.set $v (i32.load offset=12
+                  (i32.add (local.get $ptr) (i32.mul (local.get $i) (i32.const 48)))))
+                (if (i32.lt_u (local.get $v) (local.get $min))
+                  (then (local.set $min (local.get $v)) (local.set $best (local.get $i))))
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br $next)))
+            (global.set $score (i64.extend_i32_u (local.get $min)))
+            (i64.extend_i32_u (local.get $best))))
+    "#;
+
+    fn server(id: usize, in_flight: u32) -> ServerState {
+        ServerState {
+            id: ServerId::from(id),
+            name: format!("s{}", id),
+            base_latency_ms: 10,
+            weight: 1,
+            active_connections: in_flight,
+            pick_count: 0,
+            in_flight,
+            next_available_ms: 0,
+            network_latency_ms: 0,
+            healthy: true,
+        }
+    }
+
+    fn plugin(source: &str) -> Result<WasmStrategy> {
+        WasmStrategy::from_bytes(&wat::parse_str(source).expect("valid wat"))
+    }
+
+    fn select(strategy: &mut WasmStrategy, servers: &[ServerState]) -> Selection {
+        let mut rng = rand::rngs::mock::StepRng::new(0, 0);
+        let mut ctx = SelectionContext {
+            servers,
+            time_ms: 0,
+            rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Stable,
+        };
+        strategy.select(&mut ctx)
+    }
+
+    #[test]
+    fn plugin_reads_snapshot_and_returns_selection() {
+        let mut strategy = plugin(LEAST_IN_FLIGHT).expect("plugin should load");
+
+        let selection = select(&mut strategy, &[server(0, 3), server(1, 1), server(2, 2)]);
+        assert_eq!(selection.server_id, ServerId::from(1));
+        assert_eq!(selection.score, Some(1));
+
+        let selection = select(&mut strategy, &[server(0, 0), server(1, 1)]);
+        assert_eq!(selection.server_id, ServerId::from(0));
+        assert_eq!(strategy.take_error(), None);
+    }
+
+    #[test]
+    fn plugin_runaway_loop_runs_out_of_fuel() {
+        let mut strategy = plugin(
+            r#"
+            (module
+              (memory (export "memory") 1)
+              (func (export "lb_abi_version") (result i32) (i32.const 1))
+              (func (export "alloc") (param i32) (result i32) (i32.const 0))
+              (func (export "select") (param i32 i32 i64) (result i64)
+                (loop $forever (br $forever))
+                (i64.const 0)))
+            "#,
+        )
+        .expect("plugin should load");
+
+        assert_eq!(
+            select(&mut strategy, &[server(0, 0)]).server_id,
+            ServerId::from(0)
+        );
+        assert!(strategy.take_error().is_some());
+    }
+
+    #[test]
+    fn plugin_with_wrong_abi_is_rejected() {
+        let wrong_version = LEAST_IN_FLIGHT.replace(
+            r#"(result i32) (i32.const 1))"#,
+            r#"(result i32) (i32.const 2))"#,
+        );
+        assert!(matches!(plugin(&wrong_version), Err(Error::Plugin(_))));
+        assert!(matches!(
+            plugin(r#"(module (memory (export "memory") 1))"#),
+            Err(Error::Plugin(_))
+        ));
+    }
+}
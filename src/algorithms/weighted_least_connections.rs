@@ -0,0 +1,123 @@
+use rand::Rng;
+use std::cmp::Ordering;
+
+use crate::algorithms::{Selection, SelectionContext, SelectionStrategy};
+use crate::state::ServerId;
+
+/// Least-connections scored by `active_connections / weight`, so larger
+/// instances take proportionally more concurrent requests.
+#[derive(Default)]
+pub struct WeightedLeastConnectionsStrategy {
+    candidates: Vec<usize>,
+}
+
+impl SelectionStrategy for WeightedLeastConnectionsStrategy {
+    fn select(&mut self, ctx: &mut SelectionContext) -> Selection {
+        let mut best = (u64::MAX, 1u64);
+        self.candidates.clear();
+        if self.candidates.capacity() < ctx.servers.len() {
+            self.candidates
+                .reserve(ctx.servers.len().saturating_sub(self.candidates.len()));
+        }
+
+        for (idx, server) in ctx.servers.iter().enumerate() {
+            let load = (
+                u64::from(server.active_connections),
+                u64::from(server.weight.max(1)),
+            );
+            match compare_ratio(load, best) {
+                Ordering::Less => {
+                    best = load;
+                    self.candidates.clear();
+                    self.candidates.push(idx);
+                }
+                Ordering::Equal => self.candidates.push(idx),
+                Ordering::Greater => {}
+            }
+        }
+
+        let choice = if self.candidates.len() == 1 {
+            self.candidates[0]
+        } else {
+            let pick = ctx.rng.gen_range(0..self.candidates.len());
+            self.candidates[pick]
+        };
+
+        Selection {
+            server_id: ServerId::from(choice),
+            score: None,
+        }
+    }
+}
+
+/// Compares `a.0 / a.1` with `b.0 / b.1` without rounding.
+pub(crate) fn compare_ratio(a: (u64, u64), b: (u64, u64)) -> Ordering {
+    (u128::from(a.0) * u128::from(b.1)).cmp(&(u128::from(b.0) * u128::from(a.1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TieBreakConfig;
+    use crate::state::{ServerId, ServerState};
+    use rand::SeedableRng;
+
+    fn server(id: usize, weight: u32, active_connections: u32) -> ServerState {
+        ServerState {
+            id: ServerId::from(id),
+            name: format!("s{}", id),
+            base_latency_ms: 10,
+            weight,
+            active_connections,
+            pick_count: 0,
+            in_flight: active_connections,
+            next_available_ms: 0,
+            network_latency_ms: 0,
+            healthy: true,
+        }
+    }
+
+    #[test]
+    fn weighted_least_connections_scales_by_weight() {
+        let servers = vec![server(0, 1, 1), server(1, 4, 3)];
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let mut strategy = WeightedLeastConnectionsStrategy::default();
+        let mut ctx = SelectionContext {
+            servers: &servers,
+            time_ms: 0,
+            rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
+        };
+
+        assert_eq!(strategy.select(&mut ctx).server_id, ServerId::from(1));
+    }
+
+    #[test]
+    fn weighted_least_connections_uses_seeded_tiebreak() {
+        let servers = vec![server(0, 1, 1), server(1, 2, 2), server(2, 3, 3)];
+        let candidates = [0usize, 1, 2];
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let expected = {
+            let choice = rng.gen_range(0..candidates.len());
+            candidates[choice]
+        };
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        let mut strategy = WeightedLeastConnectionsStrategy::default();
+        let mut ctx = SelectionContext {
+            servers: &servers,
+            time_ms: 0,
+            rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
+        };
+
+        assert_eq!(
+            strategy.select(&mut ctx).server_id,
+            ServerId::from(expected)
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use rand::Rng;
+use std::cmp::Ordering;
+
+use crate::algorithms::weighted_least_connections::compare_ratio;
+use crate::algorithms::{Selection, SelectionContext, SelectionStrategy};
+use crate::state::ServerId;
+
+/// Least-response-time scored by expected time to completion divided by
+/// weight. The reported score is that weighted delay in ms, rounded up.
+#[derive(Default)]
+pub struct WeightedLeastResponseTimeStrategy {
+    candidates: Vec<usize>,
+}
+
+impl SelectionStrategy for WeightedLeastResponseTimeStrategy {
+    fn select(&mut self, ctx: &mut SelectionContext) -> Selection {
+        let mut best = (u64::MAX, 1u64);
+        self.candidates.clear();
+        if self.candidates.capacity() < ctx.servers.len() {
+            self.candidates
+                .reserve(ctx.servers.len().saturating_sub(self.candidates.len()));
+        }
+
+        for (idx, server) in ctx.servers.iter().enumerate() {
+            let expected_delay = server
+                .next_available_ms
+                .max(ctx.time_ms.saturating_add(server.network_latency_ms))
+                .saturating_add(server.base_latency_ms)
+                .saturating_add(server.network_latency_ms)
+                .saturating_sub(ctx.time_ms);
+            let load = (expected_delay, u64::from(server.weight.max(1)));
+            match compare_ratio(load, best) {
+                Ordering::Less => {
+                    best = load;
+                    self.candidates.clear();
+                    self.candidates.push(idx);
+                }
+                Ordering::Equal => self.candidates.push(idx),
+                Ordering::Greater => {}
+            }
+        }
+
+        let choice = if self.candidates.len() == 1 {
+            self.candidates[0]
+        } else {
+            let pick = ctx.rng.gen_range(0..self.candidates.len());
+            self.candidates[pick]
+        };
+
+        Selection {
+            server_id: ServerId::from(choice),
+            score: Some(best.0.div_ceil(best.1)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TieBreakConfig;
+    use crate::state::{ServerId, ServerState};
+    use rand::SeedableRng;
+
+    fn server(id: usize, weight: u32, base_latency_ms: u64, next_available_ms: u64) -> ServerState {
+        ServerState {
+            id: ServerId::from(id),
+            name: format!("s{}", id),
+            base_latency_ms,
+            weight,
+            active_connections: 0,
+            pick_count: 0,
+            in_flight: 0,
+            next_available_ms,
+            network_latency_ms: 0,
+            healthy: true,
+        }
+    }
+
+    #[test]
+    fn weighted_least_response_time_scales_by_weight() {
+        let servers = vec![server(0, 1, 10, 0), server(1, 4, 10, 20)];
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let mut strategy = WeightedLeastResponseTimeStrategy::default();
+        let mut ctx = SelectionContext {
+            servers: &servers,
+            time_ms: 0,
+            rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
+        };
+
+        let selection = strategy.select(&mut ctx);
+        assert_eq!(selection.server_id, ServerId::from(1));
+        assert_eq!(selection.score, Some(8));
+    }
+
+    #[test]
+    fn weighted_least_response_time_uses_seeded_tiebreak() {
+        let servers = vec![
+            server(0, 1, 10, 0),
+            server(1, 2, 20, 0),
+            server(2, 1, 30, 0),
+        ];
+        let candidates = [0usize, 1];
+        let mut rng = rand::rngs::StdRng::seed_from_u64(99);
+        let expected = {
+            let choice = rng.gen_range(0..candidates.len());
+            candidates[choice]
+        };
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(99);
+        let mut strategy = WeightedLeastResponseTimeStrategy::default();
+        let mut ctx = SelectionContext {
+            servers: &servers,
+            time_ms: 0,
+            rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
+        };
+
+        let selection = strategy.select(&mut ctx);
+        assert_eq!(selection.server_id, ServerId::from(expected));
+        assert_eq!(selection.score, Some(10));
+    }
+}
@@ -0,0 +1,105 @@
+use rand::Rng;
+
+use crate::algorithms::{SamplingRng, Selection, SelectionContext, SelectionStrategy};
+use crate::state::ServerId;
+
+/// Random selection with probability proportional to `ServerState::weight`.
+/// Weights are read on every pick, so runtime weight changes apply at once.
+#[derive(Default)]
+pub struct WeightedRandomStrategy {
+    sampling: SamplingRng,
+}
+
+impl SelectionStrategy for WeightedRandomStrategy {
+    fn select(&mut self, ctx: &mut SelectionContext) -> Selection {
+        let total: u64 = ctx.servers.iter().map(|server| server.weight as u64).sum();
+        if total == 0 {
+            let len = ctx.servers.len();
+            return Selection {
+                server_id: ServerId::from(self.sampling.rng(ctx).gen_range(0..len)),
+                score: None,
+            };
+        }
+
+        let mut target = self.sampling.rng(ctx).gen_range(0..total);
+        let mut choice = ctx.servers.len() - 1;
+        for (idx, server) in ctx.servers.iter().enumerate() {
+            let weight = server.weight as u64;
+            if target < weight {
+                choice = idx;
+                break;
+            }
+            target -= weight;
+        }
+
+        Selection {
+            server_id: ServerId::from(choice),
+            score: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TieBreakConfig;
+    use crate::state::ServerState;
+    use rand::rngs::mock::StepRng;
+    use rand::SeedableRng;
+
+    fn server(id: usize, weight: u32) -> ServerState {
+        ServerState {
+            id: ServerId::from(id),
+            name: format!("s{}", id),
+            base_latency_ms: 10,
+            weight,
+            active_connections: 0,
+            pick_count: 0,
+            in_flight: 0,
+            next_available_ms: 0,
+            network_latency_ms: 0,
+            healthy: true,
+        }
+    }
+
+    fn pick_counts(servers: &[ServerState], tie_break: TieBreakConfig, picks: usize) -> Vec<usize> {
+        let mut seeded = rand::rngs::StdRng::seed_from_u64(3);
+        let mut stable = StepRng::new(0, 0);
+        let mut strategy = WeightedRandomStrategy::default();
+        let mut counts = vec![0; servers.len()];
+        for _ in 0..picks {
+            let rng: &mut dyn rand::RngCore = match tie_break {
+                TieBreakConfig::Seeded => &mut seeded,
+                TieBreakConfig::Stable => &mut stable,
+            };
+            let mut ctx = SelectionContext {
+                servers,
+                time_ms: 0,
+                rng,
+                session_id: None,
+                request_key: None,
+                tie_break,
+            };
+            counts[usize::from(strategy.select(&mut ctx).server_id)] += 1;
+        }
+        counts
+    }
+
+    #[test]
+    fn weighted_random_follows_weights() {
+        let servers = vec![server(0, 3), server(1, 1)];
+        let counts = pick_counts(&servers, TieBreakConfig::Seeded, 4_000);
+
+        assert!((2_800..3_200).contains(&counts[0]), "{:?}", counts);
+    }
+
+    #[test]
+    fn weighted_random_spreads_picks_under_stable_tiebreak() {
+        let servers = vec![server(0, 1), server(1, 1), server(2, 2)];
+        let counts = pick_counts(&servers, TieBreakConfig::Stable, 4_000);
+
+        assert!(counts.iter().all(|&count| count > 0), "{:?}", counts);
+        assert!((1_800..2_200).contains(&counts[2]), "{:?}", counts);
+        assert_eq!(counts, pick_counts(&servers, TieBreakConfig::Stable, 4_000));
+    }
+}
@@ -54,6 +54,7 @@ impl SelectionStrategy for WeightedRoundRobinStrategy {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::TieBreakConfig;
     use crate::state::{ServerId, ServerState};
     use rand::SeedableRng;
 
@@ -69,6 +70,8 @@ mod tests {
                 pick_count: 0,
                 in_flight: 0,
                 next_available_ms: 0,
+                network_latency_ms: 0,
+                healthy: true,
             },
             ServerState {
                 id: ServerId::from(1),
@@ -79,6 +82,8 @@ mod tests {
                 pick_count: 0,
                 in_flight: 0,
                 next_available_ms: 0,
+                network_latency_ms: 0,
+                healthy: true,
             },
         ];
         let mut rng = rand::rngs::StdRng::seed_from_u64(1);
@@ -87,6 +92,9 @@ mod tests {
             servers: &servers,
             time_ms: 0,
             rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
         };
 
         let picks: Vec<ServerId> = (0..6)
@@ -116,6 +124,8 @@ mod tests {
             pick_count: 0,
             in_flight: 0,
             next_available_ms: 0,
+            network_latency_ms: 0,
+            healthy: true,
         }];
         let servers_v2 = vec![
             ServerState {
@@ -127,6 +137,8 @@ mod tests {
                 pick_count: 0,
                 in_flight: 0,
                 next_available_ms: 0,
+                network_latency_ms: 0,
+                healthy: true,
             },
             ServerState {
                 id: ServerId::from(1),
@@ -137,6 +149,8 @@ mod tests {
                 pick_count: 0,
                 in_flight: 0,
                 next_available_ms: 0,
+                network_latency_ms: 0,
+                healthy: true,
             },
         ];
         let mut rng = rand::rngs::StdRng::seed_from_u64(1);
@@ -146,6 +160,9 @@ mod tests {
                 servers: &servers_v1,
                 time_ms: 0,
                 rng: &mut rng,
+                session_id: None,
+                request_key: None,
+                tie_break: TieBreakConfig::Seeded,
             };
 
             assert_eq!(strategy.select(&mut ctx_v1).server_id, ServerId::from(0));
@@ -155,6 +172,9 @@ mod tests {
             servers: &servers_v2,
             time_ms: 0,
             rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
         };
         let picks: Vec<ServerId> = (0..2)
             .map(|_| strategy.select(&mut ctx_v2).server_id)
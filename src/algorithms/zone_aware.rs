@@ -0,0 +1,283 @@
+use crate::algorithms::subset::ServerSubset;
+use crate::algorithms::{Selection, SelectionContext, SelectionStrategy};
+use crate::models::SpilloverMode;
+use crate::state::ServerState;
+
+/// Prefers servers in the balancer's zone and spills to the remaining zones
+/// once the fraction of busy local servers reaches `spillover_threshold`.
+///
+/// In `SpilloverMode::Threshold` every request leaves the zone past the
+/// threshold. In `SpilloverMode::Proportional` the spilled fraction ramps
+/// from 0 at the threshold to the remote zones' share of capacity
+/// (`weight / base_latency_ms`) when every local server is busy; requests
+/// are spread by accumulating that fraction, so no randomness is involved.
+pub struct ZoneAwareStrategy {
+    is_local: Vec<bool>,
+    local: ServerSubset,
+    remote: ServerSubset,
+    local_strategy: Box<dyn SelectionStrategy>,
+    remote_strategy: Box<dyn SelectionStrategy>,
+    spillover_threshold: f64,
+    spillover: SpilloverMode,
+    spill_credit: f64,
+}
+
+impl ZoneAwareStrategy {
+    /// `is_local` is indexed by `ServerId`.
+    pub fn new(
+        is_local: Vec<bool>,
+        local_strategy: Box<dyn SelectionStrategy>,
+        remote_strategy: Box<dyn SelectionStrategy>,
+        spillover_threshold: f64,
+        spillover: SpilloverMode,
+    ) -> Self {
+        Self {
+            is_local,
+            local: ServerSubset::default(),
+            remote: ServerSubset::default(),
+            local_strategy,
+            remote_strategy,
+            spillover_threshold,
+            spillover,
+            spill_credit: 0.0,
+        }
+    }
+
+    fn should_spill(&mut self, ctx: &SelectionContext) -> bool {
+        let fraction = self.spill_fraction(ctx);
+        match self.spillover {
+            SpilloverMode::Threshold => fraction > 0.0,
+            SpilloverMode::Proportional => {
+                self.spill_credit += fraction;
+                if self.spill_credit >= 1.0 {
+                    self.spill_credit -= 1.0;
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    /// Fraction of requests that should leave the zone under the current load.
+    fn spill_fraction(&self, ctx: &SelectionContext) -> f64 {
+        let mut local = 0usize;
+        let mut busy = 0usize;
+        let mut local_capacity = 0.0;
+        let mut remote_capacity = 0.0;
+        for server in ctx.servers {
+            let capacity = f64::from(server.weight) / server.base_latency_ms.max(1) as f64;
+            if is_local(&self.is_local, server) {
+                local += 1;
+                local_capacity += capacity;
+                if server.in_flight > 0 {
+                    busy += 1;
+                }
+            } else {
+                remote_capacity += capacity;
+            }
+        }
+        if local == 0 {
+            return 1.0;
+        }
+        let busy_fraction = busy as f64 / local as f64;
+        if busy_fraction < self.spillover_threshold {
+            return 0.0;
+        }
+        match self.spillover {
+            SpilloverMode::Threshold => 1.0,
+            SpilloverMode::Proportional => {
+                let total_capacity = local_capacity + remote_capacity;
+                if total_capacity <= 0.0 {
+                    return 0.0;
+                }
+                let remote_share = remote_capacity / total_capacity;
+                let ramp = if self.spillover_threshold >= 1.0 {
+                    1.0
+                } else {
+                    (busy_fraction - self.spillover_threshold) / (1.0 - self.spillover_threshold)
+                };
+                remote_share * ramp
+            }
+        }
+    }
+}
+
+impl SelectionStrategy for ZoneAwareStrategy {
+    fn select(&mut self, ctx: &mut SelectionContext) -> Selection {
+        let spill = self.should_spill(ctx);
+        let local_ids = &self.is_local;
+        let local_filter = |server: &ServerState| is_local(local_ids, server);
+        if spill {
+            if let Some(selection) =
+                self.remote
+                    .select(self.remote_strategy.as_mut(), ctx, |server| {
+                        !local_filter(server)
+                    })
+            {
+                return selection;
+            }
+        }
+        self.local
+            .select(self.local_strategy.as_mut(), ctx, local_filter)
+            .or_else(|| {
+                self.remote
+                    .select(self.remote_strategy.as_mut(), ctx, |_| true)
+            })
+            .expect("selection requires at least one server")
+    }
+
+    fn on_complete(&mut self, server: &ServerState) {
+        if is_local(&self.is_local, server) {
+            self.local_strategy.on_complete(server);
+        } else {
+            self.remote_strategy.on_complete(server);
+        }
+    }
+
+    fn take_error(&mut self) -> Option<String> {
+        self.local_strategy
+            .take_error()
+            .or_else(|| self.remote_strategy.take_error())
+    }
+}
+
+fn is_local(is_local: &[bool], server: &ServerState) -> bool {
+    is_local
+        .get(usize::from(server.id))
+        .copied()
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{LeastConnectionsStrategy, RoundRobinStrategy};
+    use crate::models::TieBreakConfig;
+    use crate::state::ServerId;
+    use rand::SeedableRng;
+
+    fn server(id: usize, in_flight: u32) -> ServerState {
+        ServerState {
+            id: ServerId::from(id),
+            name: format!("s{}", id),
+            base_latency_ms: 10,
+            weight: 1,
+            active_connections: in_flight,
+            pick_count: 0,
+            in_flight,
+            next_available_ms: 0,
+            network_latency_ms: 0,
+            healthy: true,
+        }
+    }
+
+    #[test]
+    fn zone_aware_stays_local_below_threshold() {
+        let servers = vec![server(0, 0), server(1, 1), server(2, 0)];
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let mut strategy = ZoneAwareStrategy::new(
+            vec![false, true, true],
+            Box::new(RoundRobinStrategy::default()),
+            Box::new(RoundRobinStrategy::default()),
+            1.0,
+            SpilloverMode::Threshold,
+        );
+        let mut ctx = SelectionContext {
+            servers: &servers,
+            time_ms: 0,
+            rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
+        };
+
+        assert_eq!(strategy.select(&mut ctx).server_id, ServerId::from(1));
+        assert_eq!(strategy.select(&mut ctx).server_id, ServerId::from(2));
+    }
+
+    #[test]
+    fn zone_aware_spills_when_local_zone_is_busy() {
+        let servers = vec![server(0, 2), server(1, 1), server(2, 1), server(3, 0)];
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let mut strategy = ZoneAwareStrategy::new(
+            vec![false, true, true, false],
+            Box::new(LeastConnectionsStrategy::default()),
+            Box::new(LeastConnectionsStrategy::default()),
+            1.0,
+            SpilloverMode::Threshold,
+        );
+        let mut ctx = SelectionContext {
+            servers: &servers,
+            time_ms: 0,
+            rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
+        };
+
+        assert_eq!(strategy.select(&mut ctx).server_id, ServerId::from(3));
+    }
+
+    fn cross_zone_picks(local_busy: usize, spillover: SpilloverMode) -> usize {
+        let servers: Vec<ServerState> = (0..8)
+            .map(|id| server(id, u32::from(id < local_busy)))
+            .collect();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let mut strategy = ZoneAwareStrategy::new(
+            (0..8).map(|id| id < 4).collect(),
+            Box::new(RoundRobinStrategy::default()),
+            Box::new(RoundRobinStrategy::default()),
+            0.5,
+            spillover,
+        );
+        let mut ctx = SelectionContext {
+            servers: &servers,
+            time_ms: 0,
+            rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
+        };
+        (0..100)
+            .filter(|_| usize::from(strategy.select(&mut ctx).server_id) >= 4)
+            .count()
+    }
+
+    #[test]
+    fn proportional_spillover_rises_gradually_with_load() {
+        let picks: Vec<usize> = (0..=4)
+            .map(|busy| cross_zone_picks(busy, SpilloverMode::Proportional))
+            .collect();
+
+        assert_eq!(picks, vec![0, 0, 0, 25, 50]);
+        assert_eq!(cross_zone_picks(3, SpilloverMode::Threshold), 100);
+    }
+
+    #[test]
+    fn proportional_spillover_is_weighted_by_remote_capacity() {
+        let mut servers = vec![server(0, 1), server(1, 0), server(2, 0)];
+        servers[1].weight = 3;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        let mut strategy = ZoneAwareStrategy::new(
+            vec![true, false, false],
+            Box::new(RoundRobinStrategy::default()),
+            Box::new(RoundRobinStrategy::default()),
+            1.0,
+            SpilloverMode::Proportional,
+        );
+        let mut ctx = SelectionContext {
+            servers: &servers,
+            time_ms: 0,
+            rng: &mut rng,
+            session_id: None,
+            request_key: None,
+            tie_break: TieBreakConfig::Seeded,
+        };
+
+        let remote = (0..100)
+            .filter(|_| usize::from(strategy.select(&mut ctx).server_id) != 0)
+            .count();
+        assert_eq!(remote, 80);
+    }
+}
@@ -0,0 +1,194 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::events::Request;
+use crate::models::{KeyConfig, RequestProfile, SessionConfig, SessionLength, SimConfig};
+
+/// Mixed into the seed for session slot draws so they are not correlated
+/// with inter-arrival gaps drawn from the run seed.
+const SESSION_STREAM: u64 = 0x7365_7373;
+
+/// Mixed into the seed for key draws, likewise.
+const KEY_STREAM: u64 = 0x6b65_7973;
+
+/// A run's requests in arrival order, generated one at a time with their
+/// session and key already assigned, so memory does not grow with the
+/// request count.
+///
+/// Arrival times, sessions and keys each draw from their own seeded stream,
+/// so the sequence is the same however far ahead the engine pulls.
+pub struct Arrivals {
+    profile: RequestProfile,
+    next_id: usize,
+    poisson: Option<PoissonClock>,
+    sessions: Option<SessionAssigner>,
+    keys: Option<KeySampler>,
+}
+
+impl Arrivals {
+    pub fn new(config: &SimConfig) -> Self {
+        let poisson = match config.requests {
+            RequestProfile::Poisson { rate, duration_ms } => Some(PoissonClock {
+                rng: StdRng::seed_from_u64(config.seed.unwrap_or(0)),
+                lambda_ms: rate / 1000.0,
+                duration_ms,
+                time: 0.0,
+            }),
+            _ => None,
+        };
+        Self {
+            profile: config.requests.clone(),
+            next_id: 1,
+            poisson,
+            sessions: config
+                .sessions
+                .as_ref()
+                .map(|sessions| SessionAssigner::new(sessions, config.seed)),
+            keys: config
+                .keys
+                .as_ref()
+                .map(|keys| KeySampler::new(keys, config.seed)),
+        }
+    }
+
+    fn next_arrival_ms(&mut self) -> Option<u64> {
+        let idx = self.next_id - 1;
+        match self.profile {
+            RequestProfile::FixedCount(count) => (idx < count).then_some(idx as u64),
+            RequestProfile::Burst { count, at_ms } => (idx < count).then_some(at_ms),
+            RequestProfile::Poisson { .. } => self.poisson.as_mut()?.next_arrival_ms(),
+        }
+    }
+}
+
+impl Iterator for Arrivals {
+    type Item = Request;
+
+    fn next(&mut self) -> Option<Request> {
+        let arrival_time_ms = self.next_arrival_ms()?;
+        let mut request = Request {
+            id: self.next_id,
+            arrival_time_ms,
+            session_id: None,
+            session_end: false,
+            key: None,
+        };
+        self.next_id += 1;
+        if let Some(sessions) = &mut self.sessions {
+            sessions.assign(&mut request);
+        }
+        if let Some(keys) = &mut self.keys {
+            request.key = Some(keys.sample());
+        }
+        Some(request)
+    }
+}
+
+struct PoissonClock {
+    rng: StdRng,
+    lambda_ms: f64,
+    duration_ms: u64,
+    time: f64,
+}
+
+impl PoissonClock {
+    fn next_arrival_ms(&mut self) -> Option<u64> {
+        if self.time >= self.duration_ms as f64 {
+            return None;
+        }
+        let mut u = self.rng.gen::<f64>();
+        if u <= f64::MIN_POSITIVE {
+            u = f64::MIN_POSITIVE;
+        }
+        self.time += -u.ln() / self.lambda_ms;
+        (self.time < self.duration_ms as f64).then_some(self.time.floor() as u64)
+    }
+}
+
+/// Spreads arrivals over `concurrent` open sessions; a slot opens a new
+/// session once its current one has had all its requests.
+struct SessionAssigner {
+    rng: StdRng,
+    config: SessionConfig,
+    slots: Vec<(u64, u64)>,
+    next_session_id: u64,
+}
+
+impl SessionAssigner {
+    fn new(config: &SessionConfig, seed: Option<u64>) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed.unwrap_or(0) ^ SESSION_STREAM),
+            config: config.clone(),
+            slots: vec![(0, 0); config.concurrent],
+            next_session_id: 1,
+        }
+    }
+
+    fn assign(&mut self, request: &mut Request) {
+        let slot = &mut self.slots[self.rng.gen_range(0..self.config.concurrent)];
+        if slot.1 == 0 {
+            *slot = (
+                self.next_session_id,
+                session_length(&mut self.rng, &self.config),
+            );
+            self.next_session_id += 1;
+        }
+        request.session_id = Some(slot.0);
+        slot.1 -= 1;
+        request.session_end = slot.1 == 0;
+    }
+}
+
+fn session_length(rng: &mut StdRng, config: &SessionConfig) -> u64 {
+    match config.length {
+        SessionLength::Fixed => (config.mean_length.round() as u64).max(1),
+        SessionLength::Geometric => {
+            if config.mean_length <= 1.0 {
+                return 1;
+            }
+            let p = 1.0 / config.mean_length;
+            let mut u = rng.gen::<f64>();
+            if u <= f64::MIN_POSITIVE {
+                u = f64::MIN_POSITIVE;
+            }
+            1 + (u.ln() / (1.0 - p).ln()).floor() as u64
+        }
+    }
+}
+
+/// Draws keys uniformly, or Zipf-distributed over `count` ranks.
+struct KeySampler {
+    rng: StdRng,
+    count: u64,
+    cdf: Vec<f64>,
+}
+
+impl KeySampler {
+    fn new(config: &KeyConfig, seed: Option<u64>) -> Self {
+        let mut total = 0.0;
+        let cdf = if config.zipf_exponent == 0.0 {
+            Vec::new()
+        } else {
+            (1..=config.count)
+                .map(|rank| {
+                    total += 1.0 / (rank as f64).powf(config.zipf_exponent);
+                    total
+                })
+                .collect()
+        };
+        Self {
+            rng: StdRng::seed_from_u64(seed.unwrap_or(0) ^ KEY_STREAM),
+            count: config.count,
+            cdf,
+        }
+    }
+
+    fn sample(&mut self) -> u64 {
+        let Some(&total) = self.cdf.last() else {
+            return self.rng.gen_range(0..self.count);
+        };
+        let target = self.rng.gen::<f64>() * total;
+        let rank = self.cdf.partition_point(|&cumulative| cumulative <= target);
+        rank.min(self.cdf.len() - 1) as u64
+    }
+}
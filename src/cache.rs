@@ -0,0 +1,71 @@
+use std::collections::{BTreeMap, HashMap};
+
+/// Fixed-capacity least-recently-used set of request keys.
+#[derive(Clone, Debug)]
+pub struct LruCache {
+    capacity: usize,
+    tick: u64,
+    stamps: HashMap<u64, u64>,
+    order: BTreeMap<u64, u64>,
+}
+
+impl LruCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            tick: 0,
+            stamps: HashMap::with_capacity(capacity),
+            order: BTreeMap::new(),
+        }
+    }
+
+    /// Looks up `key`, marking it most recently used. Returns whether it was
+    /// cached; a miss inserts it, evicting the least recently used key when
+    /// the cache is full.
+    pub fn access(&mut self, key: u64) -> bool {
+        if self.capacity == 0 {
+            return false;
+        }
+        self.tick += 1;
+        if let Some(stamp) = self.stamps.insert(key, self.tick) {
+            self.order.remove(&stamp);
+            self.order.insert(self.tick, key);
+            return true;
+        }
+        if self.stamps.len() > self.capacity {
+            if let Some((_, evicted)) = self.order.pop_first() {
+                self.stamps.remove(&evicted);
+            }
+        }
+        self.order.insert(self.tick, key);
+        false
+    }
+
+    pub fn len(&self) -> usize {
+        self.stamps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stamps.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lru_cache_evicts_least_recently_used_key() {
+        let mut cache = LruCache::new(2);
+
+        assert!(!cache.access(1));
+        assert!(!cache.access(2));
+        assert!(cache.access(1));
+        assert!(!cache.access(3));
+        assert_eq!(cache.len(), 2);
+
+        assert!(cache.access(1));
+        assert!(!cache.access(2));
+        assert!(!cache.access(3));
+    }
+}
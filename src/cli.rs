@@ -0,0 +1,267 @@
+use std::borrow::Cow;
+use std::fs;
+use std::path::Path;
+
+#[cfg(feature = "scripting")]
+use crate::algorithms::ScriptStrategy;
+#[cfg(feature = "wasm")]
+use crate::algorithms::WasmStrategy;
+use crate::algorithms::{StrategyRegistration, StrategyRegistry};
+use crate::compare::run_comparison;
+use crate::config::{
+    self, format_config, Command, CompareArgs, FormatArg, ListArgs, RunArgs, SweepArgs,
+};
+use crate::engine;
+use crate::error::{Error, Result};
+use crate::event_log::EventLog;
+use crate::output::{
+    format_comparison, format_replications, format_sweep, format_timeseries, formatter_from_format,
+};
+use crate::replication::run_replications;
+use crate::sweep::{run_sweep, SweepAxis};
+
+/// Entry point of the `lb-sim` binary. Programs embedding lb-sim can call this
+/// with a registry holding their own strategies to get the same CLI.
+pub fn main(registry: &StrategyRegistry) {
+    if let Err(err) = run(registry) {
+        eprintln!("Error: {}", err);
+        std::process::exit(1);
+    }
+}
+
+pub fn run(registry: &StrategyRegistry) -> Result<()> {
+    let command = config::parse_command()?;
+
+    match command {
+        Command::Run(run_args) => run_simulation(run_args, registry),
+        Command::ListAlgorithms(list_args) => list_algorithms(list_args, registry),
+        Command::ShowConfig(run_args) => show_config(run_args, registry),
+        Command::Compare(compare_args) => compare(compare_args, registry),
+        Command::Sweep(sweep_args) => sweep(sweep_args, registry),
+    }
+}
+
+fn run_simulation(run_args: RunArgs, registry: &StrategyRegistry) -> Result<()> {
+    let registry = with_user_strategies(registry, &run_args)?;
+    let replications = run_args.replications;
+    let event_log_path = run_args.event_log.clone();
+    let timeseries_path = run_args.timeseries_out.clone();
+    let (config, format_for_engine) = config::build_config_from_run_args(run_args)?;
+    let samples_timeseries = config
+        .metrics
+        .as_ref()
+        .is_some_and(|metrics| metrics.timeseries.is_some());
+    if timeseries_path.is_some() && !samples_timeseries {
+        return Err(Error::Cli(
+            "--timeseries-out needs --timeseries (or timeseries in [metrics])".to_string(),
+        ));
+    }
+    if let Some(replications) = replications {
+        if replications == 0 {
+            return Err(Error::Cli(
+                "--replications must be greater than 0".to_string(),
+            ));
+        }
+        if event_log_path.is_some() {
+            return Err(Error::Cli(
+                "--event-log cannot be combined with --replications".to_string(),
+            ));
+        }
+        if timeseries_path.is_some() {
+            return Err(Error::Cli(
+                "--timeseries-out cannot be combined with --replications".to_string(),
+            ));
+        }
+        let report = run_replications(&config, &registry, replications)?;
+        print!("{}", format_replications(&report, &format_for_engine));
+        return Ok(());
+    }
+    // With an event log the assignments are streamed there instead, so long
+    // logged runs do not also keep every one of them in memory.
+    let store_assignments = event_log_path.is_none()
+        && !matches!(
+            format_for_engine,
+            FormatArg::Summary | FormatArg::Markdown | FormatArg::Csv
+        );
+    let result = match event_log_path {
+        Some(path) => engine::run_simulation_with_event_log(
+            &config,
+            &registry,
+            store_assignments,
+            EventLog::create(&path)?,
+        )?,
+        None => engine::run_simulation_with_registry(&config, &registry, store_assignments)?,
+    };
+    if let (Some(path), Some(timeseries)) = (&timeseries_path, &result.timeseries) {
+        fs::write(path, format_timeseries(timeseries)).map_err(|err| {
+            Error::TimeSeriesIo(format!(
+                "failed to write time series '{}': {}",
+                path.display(),
+                err
+            ))
+        })?;
+    }
+    let formatter = formatter_from_format(&format_for_engine);
+    let output = formatter.write(&result);
+    print!("{}", output);
+
+    Ok(())
+}
+
+fn list_algorithms(list_args: ListArgs, registry: &StrategyRegistry) -> Result<()> {
+    for registration in registry.iter() {
+        println!("{}", registration.name());
+        if list_args.verbose {
+            println!("  {}", registration.description());
+            if !registration.aliases().is_empty() {
+                println!("  aliases: {}", registration.aliases().join(", "));
+            }
+            for param in registration.params() {
+                let default_label = match &param.default {
+                    Some(default) => format!(", default {}", default),
+                    None => String::new(),
+                };
+                println!(
+                    "  {} ({}{}): {}",
+                    param.name, param.kind, default_label, param.description
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn compare(compare_args: CompareArgs, registry: &StrategyRegistry) -> Result<()> {
+    let CompareArgs {
+        algos,
+        mut scenario,
+    } = compare_args;
+    let registry = with_user_strategies(registry, &scenario)?;
+    reject_single_run_outputs(&scenario, "compare")?;
+    if scenario.algo.is_none() && !scenario.algo_param.is_empty() {
+        return Err(Error::Cli(
+            "--algo-param needs --algo to name the algorithm it applies to".to_string(),
+        ));
+    }
+    if scenario.algo.is_none() && scenario.config.is_none() {
+        // The scenario needs some algorithm to build; each run replaces it.
+        let placeholder = algos.first().map(String::as_str).or_else(|| {
+            registry
+                .iter()
+                .next()
+                .map(|registration| registration.name())
+        });
+        scenario.algo = placeholder.map(str::to_string);
+    }
+    let (config, format) = config::build_config_from_run_args(scenario)?;
+    let rows = run_comparison(&config, &registry, &algos)?;
+    print!("{}", format_comparison(&rows, &format));
+    Ok(())
+}
+
+fn sweep(sweep_args: SweepArgs, registry: &StrategyRegistry) -> Result<()> {
+    let SweepArgs { axes, mut scenario } = sweep_args;
+    let registry = with_user_strategies(registry, &scenario)?;
+    reject_single_run_outputs(&scenario, "sweep")?;
+    let axes = axes
+        .iter()
+        .map(|spec| SweepAxis::parse(spec))
+        .collect::<Result<Vec<_>>>()?;
+    let sweeps_overload = axes.iter().any(|axis| axis.name == "overload_factor");
+    if sweeps_overload && scenario.requests.is_none() && scenario.burst.is_none() {
+        scenario.overload = true;
+    }
+    if scenario.algo.is_none() && scenario.config.is_none() {
+        // An `algo` axis replaces the algorithm of every run.
+        scenario.algo = axes
+            .iter()
+            .find(|axis| axis.name == "algo")
+            .and_then(|axis| axis.values.first())
+            .and_then(|value| value.as_str())
+            .map(str::to_string);
+    }
+    let (config, format) = config::build_config_from_run_args(scenario)?;
+    let rows = run_sweep(&config, &registry, &axes)?;
+    print!("{}", format_sweep(&axes, &rows, &format));
+    Ok(())
+}
+
+/// Compare and sweep run many simulations, so a single event log would mix
+/// them.
+fn reject_single_run_outputs(scenario: &RunArgs, command: &str) -> Result<()> {
+    if scenario.event_log.is_some() {
+        return Err(Error::Cli(format!(
+            "--event-log applies to a single run, not {}",
+            command
+        )));
+    }
+    if scenario.timeseries_out.is_some() {
+        return Err(Error::Cli(format!(
+            "--timeseries-out applies to a single run, not {}",
+            command
+        )));
+    }
+    Ok(())
+}
+
+fn show_config(run_args: RunArgs, registry: &StrategyRegistry) -> Result<()> {
+    let registry = with_user_strategies(registry, &run_args)?;
+    let (config, _) = config::build_config_from_run_args(run_args)?;
+    registry.resolve_algo(&config.algo)?;
+    let output = format_config(&config);
+    print!("{}", output);
+    Ok(())
+}
+
+/// Adds the `--script` and `--plugin` strategies to `registry`.
+fn with_user_strategies<'a>(
+    registry: &'a StrategyRegistry,
+    run_args: &RunArgs,
+) -> Result<Cow<'a, StrategyRegistry>> {
+    if run_args.script.is_empty() && run_args.plugin.is_empty() {
+        return Ok(Cow::Borrowed(registry));
+    }
+    let mut registry = registry.clone();
+    for entry in &run_args.script {
+        let (name, path) = name_and_path(entry, "--script")?;
+        registry.register(script_registration(name, path)?)?;
+    }
+    for entry in &run_args.plugin {
+        let (name, path) = name_and_path(entry, "--plugin")?;
+        registry.register(plugin_registration(name, path)?)?;
+    }
+    Ok(Cow::Owned(registry))
+}
+
+fn name_and_path<'a>(entry: &'a str, flag: &str) -> Result<(&'a str, &'a Path)> {
+    entry
+        .split_once('=')
+        .map(|(name, path)| (name.trim(), path.trim()))
+        .filter(|(name, path)| !name.is_empty() && !path.is_empty())
+        .map(|(name, path)| (name, Path::new(path)))
+        .ok_or_else(|| Error::Cli(format!("invalid {} '{}': expected name=path", flag, entry)))
+}
+
+#[cfg(feature = "scripting")]
+fn script_registration(name: &str, path: &Path) -> Result<StrategyRegistration> {
+    ScriptStrategy::registration(name, path)
+}
+
+#[cfg(not(feature = "scripting"))]
+fn script_registration(_name: &str, _path: &Path) -> Result<StrategyRegistration> {
+    Err(Error::Cli(
+        "--script requires lb-sim built with the `scripting` feature".to_string(),
+    ))
+}
+
+#[cfg(feature = "wasm")]
+fn plugin_registration(name: &str, path: &Path) -> Result<StrategyRegistration> {
+    WasmStrategy::registration(name, path)
+}
+
+#[cfg(not(feature = "wasm"))]
+fn plugin_registration(_name: &str, _path: &Path) -> Result<StrategyRegistration> {
+    Err(Error::Cli(
+        "--plugin requires lb-sim built with the `wasm` feature".to_string(),
+    ))
+}
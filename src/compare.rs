@@ -0,0 +1,150 @@
+use serde::Serialize;
+
+use crate::algorithms::StrategyRegistry;
+use crate::engine::run_simulation_with_registry;
+use crate::error::Result;
+use crate::models::{AlgoConfig, Percentile, SimConfig};
+use crate::state::SimulationResult;
+
+/// Headline metrics of one run, one row of a comparison table.
+#[derive(Clone, Debug, Serialize)]
+pub struct ComparisonRow {
+    pub algo: String,
+    pub p95_ms: Option<u64>,
+    pub p99_ms: Option<u64>,
+    pub throughput_rps: f64,
+    pub jain_fairness: f64,
+    pub avg_wait_ms: u64,
+}
+
+impl ComparisonRow {
+    pub fn from_result(result: &SimulationResult) -> Self {
+        let metrics = &result.phase1_metrics;
+        Self {
+            algo: result.metadata.algo.clone(),
+            p95_ms: metrics.response_time.p95_ms(),
+            p99_ms: metrics.response_time.p99_ms(),
+            throughput_rps: metrics.throughput_rps,
+            jain_fairness: metrics.jain_fairness,
+            avg_wait_ms: metrics.avg_wait_ms,
+        }
+    }
+}
+
+/// Adds p95 and p99 to the percentiles `config` reports, so the headline
+/// columns are filled whatever percentiles the scenario asks for.
+pub fn add_headline_percentiles(config: &mut SimConfig) {
+    let metrics = config.metrics.get_or_insert_with(Default::default);
+    for value in [95.0, 99.0] {
+        if !metrics
+            .percentiles
+            .iter()
+            .any(|percentile| percentile.value() == value)
+        {
+            metrics.percentiles.extend(Percentile::new(value));
+        }
+    }
+}
+
+/// Runs `config` once per algorithm in `algos`, or per registered algorithm
+/// when `algos` is empty, keeping everything else (including the seed) fixed.
+/// `config.algo`'s params are kept for the algorithm it names.
+pub fn run_comparison(
+    config: &SimConfig,
+    registry: &StrategyRegistry,
+    algos: &[String],
+) -> Result<Vec<ComparisonRow>> {
+    let names: Vec<&str> = if algos.is_empty() {
+        registry
+            .iter()
+            .map(|registration| registration.name())
+            .collect()
+    } else {
+        algos.iter().map(String::as_str).collect()
+    };
+
+    let mut rows = Vec::with_capacity(names.len());
+    for name in names {
+        let mut variant = config.clone();
+        if name != config.algo.name() {
+            variant.algo = AlgoConfig::new(name);
+        }
+        add_headline_percentiles(&mut variant);
+        let result = run_simulation_with_registry(&variant, registry, false)?;
+        rows.push(ComparisonRow::from_result(&result));
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use crate::models::{MetricsConfig, RequestProfile, TieBreakConfig};
+    use crate::test_support;
+
+    fn scenario() -> SimConfig {
+        SimConfig {
+            tie_break: TieBreakConfig::Seeded,
+            ..test_support::scenario(
+                RequestProfile::Poisson {
+                    rate: 120.0,
+                    duration_ms: 1_000,
+                },
+                AlgoConfig::new("join-shortest-queue").with_param("d", 3),
+            )
+        }
+    }
+
+    #[test]
+    fn comparison_runs_every_registered_algorithm() {
+        let registry = StrategyRegistry::builtin();
+        let rows = run_comparison(&scenario(), &registry, &[]).expect("comparison should run");
+
+        let names: Vec<&str> = rows.iter().map(|row| row.algo.as_str()).collect();
+        assert_eq!(names.len(), registry.iter().count());
+        assert_eq!(names[0], "round-robin");
+        assert!(names.contains(&"join-shortest-queue (d=3)"));
+        let round_robin = &rows[0];
+        let least_response_time = rows
+            .iter()
+            .find(|row| row.algo == "least-response-time")
+            .expect("least-response-time row");
+        assert!(least_response_time.p99_ms < round_robin.p99_ms);
+    }
+
+    #[test]
+    fn comparison_reports_p95_and_p99_without_configured_percentiles() {
+        let registry = StrategyRegistry::builtin();
+        let mut config = scenario();
+        config.metrics = Some(MetricsConfig {
+            percentiles: vec![
+                Percentile::new(50.0).unwrap(),
+                Percentile::new(90.0).unwrap(),
+            ],
+            ..MetricsConfig::default()
+        });
+        let rows = run_comparison(&config, &registry, &["round-robin".to_string()])
+            .expect("comparison should run");
+
+        assert!(rows[0].p95_ms.is_some());
+        assert!(rows[0].p99_ms.is_some());
+    }
+
+    #[test]
+    fn comparison_runs_chosen_subset_with_same_seed() {
+        let registry = StrategyRegistry::builtin();
+        let algos = ["random".to_string(), "least-connections".to_string()];
+        let first = run_comparison(&scenario(), &registry, &algos).expect("comparison should run");
+        let second = run_comparison(&scenario(), &registry, &algos).expect("comparison should run");
+
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[0].algo, "random");
+        assert_eq!(first[0].p99_ms, second[0].p99_ms);
+        assert!(first[0].p95_ms.is_some());
+        assert!(matches!(
+            run_comparison(&scenario(), &registry, &["fastest".to_string()]),
+            Err(Error::UnknownAlgorithm(_))
+        ));
+    }
+}
@@ -4,7 +4,14 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::error::{Error, Result};
-use crate::models::{AlgoConfig, RequestProfile, ServerConfig, SimConfig, TieBreakConfig};
+use crate::models::{
+    default_cache_hit_latency_ms, default_concurrent_sessions, default_rate_limit_burst,
+    default_spillover_threshold, AdaptiveLimitConfig, AdmissionConfig, AlgoConfig, BalancerConfig,
+    CacheConfig, DistributionKind, FailoverConfig, KeyConfig, LatencyBackend, LocalityConfig,
+    MeasurementConfig, MetricsConfig, OutageWindow, ParamValue, Percentile, RequestProfile,
+    ServerConfig, SessionConfig, SessionLength, SimConfig, SpilloverMode, TieBreakConfig,
+    TimeSeriesSampling, TokenBucketConfig,
+};
 
 const SERVER_SPEC_VALUE_NAME: &str = "name:latency[:weight]";
 
@@ -13,8 +20,26 @@ const SERVER_SPEC_VALUE_NAME: &str = "name:latency[:weight]";
 pub struct CliArgs {
     #[command(subcommand)]
     pub command: Option<Command>,
-    #[arg(long, value_enum)]
-    pub algo: Option<AlgoArg>,
+    #[arg(long, help = "Selection strategy (see list-algorithms)")]
+    pub algo: Option<String>,
+    #[arg(
+        long,
+        value_name = "key=value",
+        help = "Strategy parameter (see list-algorithms --verbose)"
+    )]
+    pub algo_param: Vec<String>,
+    #[arg(
+        long,
+        value_name = "name=path",
+        help = "Register a Rhai script as a strategy named `name` (needs the scripting feature)"
+    )]
+    pub script: Vec<String>,
+    #[arg(
+        long,
+        value_name = "name=path",
+        help = "Register a WebAssembly plugin as a strategy named `name` (needs the wasm feature)"
+    )]
+    pub plugin: Vec<String>,
     #[arg(long)]
     pub servers: Option<String>,
     #[arg(long, value_name = SERVER_SPEC_VALUE_NAME)]
@@ -44,8 +69,155 @@ pub struct CliArgs {
         help = "Seed tie-breaks for least-connections/response-time; omit for stable input-order tie-breaks"
     )]
     pub seed: Option<u64>,
+    #[arg(
+        long,
+        help = "Run N replications in parallel with seeds seed, seed+1, ... and report mean, std dev, and 95% CI"
+    )]
+    pub replications: Option<usize>,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Stream every processed event to PATH as newline-delimited JSON, instead of listing assignments on stdout"
+    )]
+    pub event_log: Option<PathBuf>,
     #[arg(long)]
     pub config: Option<PathBuf>,
+    #[arg(
+        long,
+        help = "Number of independent load balancers sharing the servers"
+    )]
+    pub balancers: Option<usize>,
+    #[arg(
+        long,
+        help = "Refresh every balancer's view of server load at this interval in ms"
+    )]
+    pub sync_interval_ms: Option<u64>,
+    #[arg(
+        long,
+        help = "Delay in ms before a view sync reaches the balancers [default: 0]"
+    )]
+    pub sync_delay_ms: Option<u64>,
+    #[arg(
+        long,
+        help = "One-way network latency in ms between balancer and every server"
+    )]
+    pub network_ms: Option<u64>,
+    #[arg(long, help = "Uniform jitter in ms added to each network hop")]
+    pub network_jitter_ms: Option<u64>,
+    #[arg(long, value_name = "name=zone", help = "Place a server in a zone")]
+    pub server_zone: Vec<String>,
+    #[arg(long, help = "Zone of the load balancer; enables zone-aware routing")]
+    pub zone: Option<String>,
+    #[arg(
+        long,
+        help = "Busy fraction of local servers at which traffic spills to other zones"
+    )]
+    pub spillover_threshold: Option<f64>,
+    #[arg(
+        long,
+        help = "Past the threshold, spill everything (threshold) or a load-scaled, capacity-weighted fraction (proportional)"
+    )]
+    pub spillover: Option<SpilloverArg>,
+    #[arg(
+        long,
+        value_name = "name=priority",
+        help = "Place a server in a failover tier"
+    )]
+    pub server_priority: Vec<String>,
+    #[arg(
+        long,
+        value_name = "name=start..end",
+        help = "Take a server down for [start, end) ms"
+    )]
+    pub outage: Vec<String>,
+    #[arg(
+        long,
+        help = "Healthy fraction a priority tier needs to keep receiving traffic"
+    )]
+    pub healthy_threshold: Option<f64>,
+    #[arg(
+        long,
+        help = "Mean session length in requests; enables sticky sessions"
+    )]
+    pub session_length: Option<f64>,
+    #[arg(
+        long,
+        value_enum,
+        help = "Session length distribution (default geometric)"
+    )]
+    pub session_length_dist: Option<SessionLengthArg>,
+    #[arg(long, help = "Number of concurrently open sessions")]
+    pub sessions: Option<usize>,
+    #[arg(
+        long,
+        help = "Break stickiness when the pinned server has this many requests in flight"
+    )]
+    pub sticky_max_in_flight: Option<u32>,
+    #[arg(long, help = "Give each request one of this many keys")]
+    pub keys: Option<u64>,
+    #[arg(long, help = "Zipf exponent for key popularity (0 = uniform)")]
+    pub key_zipf: Option<f64>,
+    #[arg(long, help = "Keys each server caches (LRU); requires --keys")]
+    pub cache_size: Option<usize>,
+    #[arg(long, help = "Service time of a cache hit in ms [default: 1]")]
+    pub cache_hit_ms: Option<u64>,
+    #[arg(long, help = "Reject arrivals while this many requests are in flight")]
+    pub max_in_flight: Option<u32>,
+    #[arg(long, help = "Token-bucket rate limit in requests per second")]
+    pub rate_limit: Option<f64>,
+    #[arg(long, help = "Token-bucket size for --rate-limit [default: 1]")]
+    pub rate_limit_burst: Option<u32>,
+    #[arg(
+        long,
+        help = "Enable an AIMD concurrency limit that backs off above this response time"
+    )]
+    pub adaptive_limit_ms: Option<u64>,
+    #[arg(
+        long,
+        help = "Leave requests arriving in the first N ms out of the metrics"
+    )]
+    pub warmup_ms: Option<u64>,
+    #[arg(
+        long,
+        help = "Only measure requests arriving within N ms after the warm-up"
+    )]
+    pub measure_window_ms: Option<u64>,
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "p50,p99.9,max",
+        help = "Response-time percentiles to report [default: p95,p99]"
+    )]
+    pub percentiles: Vec<String>,
+    #[arg(
+        long,
+        value_enum,
+        help = "Also report every response time as a histogram or CDF (JSON output)"
+    )]
+    pub latency_distribution: Option<DistributionArg>,
+    #[arg(
+        long,
+        value_enum,
+        help = "Keep every response time (exact) or a bounded-memory HDR histogram [default: exact]"
+    )]
+    pub latency_backend: Option<LatencyBackendArg>,
+    #[arg(
+        long,
+        help = "Significant digits of the HDR histogram, 1 to 5 [default: 3]"
+    )]
+    pub hdr_digits: Option<u8>,
+    #[arg(
+        long,
+        value_name = "MS|events",
+        help = "Sample each server's in-flight requests, connections and busy state at this interval, or on every change (JSON output, or --timeseries-out)"
+    )]
+    pub timeseries: Option<String>,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Write the --timeseries samples to PATH as CSV: time_ms,server,in_flight,active_connections,busy"
+    )]
+    pub timeseries_out: Option<PathBuf>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -53,15 +225,67 @@ pub enum Command {
     /// Run the load balancer simulation
     Run(RunArgs),
     /// List available algorithms
-    ListAlgorithms,
+    ListAlgorithms(ListArgs),
     /// Show the effective configuration
     ShowConfig(RunArgs),
+    /// Run several algorithms on the same scenario and compare their metrics
+    Compare(CompareArgs),
+    /// Run the scenario once per combination of swept settings
+    Sweep(SweepArgs),
+}
+
+#[derive(Parser, Debug)]
+pub struct CompareArgs {
+    #[arg(
+        long,
+        value_delimiter = ',',
+        help = "Comma-separated algorithms to compare [default: all]"
+    )]
+    pub algos: Vec<String>,
+    #[command(flatten)]
+    pub scenario: RunArgs,
+}
+
+#[derive(Parser, Debug)]
+pub struct SweepArgs {
+    #[arg(
+        required = true,
+        value_name = "AXIS",
+        help = "Setting to sweep: name=[a,b,...], name=start..end [step s], or name=value"
+    )]
+    pub axes: Vec<String>,
+    #[command(flatten)]
+    pub scenario: RunArgs,
+}
+
+#[derive(Parser, Debug)]
+pub struct ListArgs {
+    #[arg(long, help = "Also print each algorithm's description and parameters")]
+    pub verbose: bool,
 }
 
 #[derive(Parser, Debug)]
 pub struct RunArgs {
-    #[arg(long, value_enum)]
-    pub algo: Option<AlgoArg>,
+    #[arg(long, help = "Selection strategy (see list-algorithms)")]
+    pub algo: Option<String>,
+    #[arg(
+        long,
+        value_name = "key=value",
+        help = "Strategy parameter (see list-algorithms --verbose)"
+    )]
+    pub algo_param: Vec<String>,
+    #[arg(
+        long,
+        value_name = "name=path",
+        help = "Register a Rhai script as a strategy named `name` (needs the scripting feature)"
+    )]
+    pub script: Vec<String>,
+    #[arg(
+        long,
+        value_name = "name=path",
+        help = "Register a WebAssembly plugin as a strategy named `name` (needs the wasm feature)"
+    )]
+    pub plugin: Vec<String>,
     #[arg(long)]
     pub servers: Option<String>,
     #[arg(long, value_name = SERVER_SPEC_VALUE_NAME)]
@@ -91,36 +315,226 @@ pub struct RunArgs {
         help = "Seed tie-breaks for least-connections/response-time; omit for stable input-order tie-breaks"
     )]
     pub seed: Option<u64>,
+    #[arg(
+        long,
+        help = "Run N replications in parallel with seeds seed, seed+1, ... and report mean, std dev, and 95% CI"
+    )]
+    pub replications: Option<usize>,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Stream every processed event to PATH as newline-delimited JSON, instead of listing assignments on stdout"
+    )]
+    pub event_log: Option<PathBuf>,
     #[arg(long)]
     pub config: Option<PathBuf>,
+    #[arg(
+        long,
+        help = "Number of independent load balancers sharing the servers"
+    )]
+    pub balancers: Option<usize>,
+    #[arg(
+        long,
+        help = "Refresh every balancer's view of server load at this interval in ms"
+    )]
+    pub sync_interval_ms: Option<u64>,
+    #[arg(
+        long,
+        help = "Delay in ms before a view sync reaches the balancers [default: 0]"
+    )]
+    pub sync_delay_ms: Option<u64>,
+    #[arg(
+        long,
+        help = "One-way network latency in ms between balancer and every server"
+    )]
+    pub network_ms: Option<u64>,
+    #[arg(long, help = "Uniform jitter in ms added to each network hop")]
+    pub network_jitter_ms: Option<u64>,
+    #[arg(long, value_name = "name=zone", help = "Place a server in a zone")]
+    pub server_zone: Vec<String>,
+    #[arg(long, help = "Zone of the load balancer; enables zone-aware routing")]
+    pub zone: Option<String>,
+    #[arg(
+        long,
+        help = "Busy fraction of local servers at which traffic spills to other zones"
+    )]
+    pub spillover_threshold: Option<f64>,
+    #[arg(
+        long,
+        help = "Past the threshold, spill everything (threshold) or a load-scaled, capacity-weighted fraction (proportional)"
+    )]
+    pub spillover: Option<SpilloverArg>,
+    #[arg(
+        long,
+        value_name = "name=priority",
+        help = "Place a server in a failover tier"
+    )]
+    pub server_priority: Vec<String>,
+    #[arg(
+        long,
+        value_name = "name=start..end",
+        help = "Take a server down for [start, end) ms"
+    )]
+    pub outage: Vec<String>,
+    #[arg(
+        long,
+        help = "Healthy fraction a priority tier needs to keep receiving traffic"
+    )]
+    pub healthy_threshold: Option<f64>,
+    #[arg(
+        long,
+        help = "Mean session length in requests; enables sticky sessions"
+    )]
+    pub session_length: Option<f64>,
+    #[arg(
+        long,
+        value_enum,
+        help = "Session length distribution (default geometric)"
+    )]
+    pub session_length_dist: Option<SessionLengthArg>,
+    #[arg(long, help = "Number of concurrently open sessions")]
+    pub sessions: Option<usize>,
+    #[arg(
+        long,
+        help = "Break stickiness when the pinned server has this many requests in flight"
+    )]
+    pub sticky_max_in_flight: Option<u32>,
+    #[arg(long, help = "Give each request one of this many keys")]
+    pub keys: Option<u64>,
+    #[arg(long, help = "Zipf exponent for key popularity (0 = uniform)")]
+    pub key_zipf: Option<f64>,
+    #[arg(long, help = "Keys each server caches (LRU); requires --keys")]
+    pub cache_size: Option<usize>,
+    #[arg(long, help = "Service time of a cache hit in ms [default: 1]")]
+    pub cache_hit_ms: Option<u64>,
+    #[arg(long, help = "Reject arrivals while this many requests are in flight")]
+    pub max_in_flight: Option<u32>,
+    #[arg(long, help = "Token-bucket rate limit in requests per second")]
+    pub rate_limit: Option<f64>,
+    #[arg(long, help = "Token-bucket size for --rate-limit [default: 1]")]
+    pub rate_limit_burst: Option<u32>,
+    #[arg(
+        long,
+        help = "Enable an AIMD concurrency limit that backs off above this response time"
+    )]
+    pub adaptive_limit_ms: Option<u64>,
+    #[arg(
+        long,
+        help = "Leave requests arriving in the first N ms out of the metrics"
+    )]
+    pub warmup_ms: Option<u64>,
+    #[arg(
+        long,
+        help = "Only measure requests arriving within N ms after the warm-up"
+    )]
+    pub measure_window_ms: Option<u64>,
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "p50,p99.9,max",
+        help = "Response-time percentiles to report [default: p95,p99]"
+    )]
+    pub percentiles: Vec<String>,
+    #[arg(
+        long,
+        value_enum,
+        help = "Also report every response time as a histogram or CDF (JSON output)"
+    )]
+    pub latency_distribution: Option<DistributionArg>,
+    #[arg(
+        long,
+        value_enum,
+        help = "Keep every response time (exact) or a bounded-memory HDR histogram [default: exact]"
+    )]
+    pub latency_backend: Option<LatencyBackendArg>,
+    #[arg(
+        long,
+        help = "Significant digits of the HDR histogram, 1 to 5 [default: 3]"
+    )]
+    pub hdr_digits: Option<u8>,
+    #[arg(
+        long,
+        value_name = "MS|events",
+        help = "Sample each server's in-flight requests, connections and busy state at this interval, or on every change (JSON output, or --timeseries-out)"
+    )]
+    pub timeseries: Option<String>,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Write the --timeseries samples to PATH as CSV: time_ms,server,in_flight,active_connections,busy"
+    )]
+    pub timeseries_out: Option<PathBuf>,
 }
 
-#[derive(ValueEnum, Clone, Debug)]
-pub enum AlgoArg {
-    RoundRobin,
-    WeightedRoundRobin,
-    LeastConnections,
-    LeastResponseTime,
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum SessionLengthArg {
+    Fixed,
+    Geometric,
 }
 
-#[derive(ValueEnum, Clone, Debug, PartialEq, Eq)]
-pub enum FormatArg {
-    Human,
-    Summary,
-    Json,
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum DistributionArg {
+    Histogram,
+    Cdf,
+}
+
+impl From<DistributionArg> for DistributionKind {
+    fn from(value: DistributionArg) -> Self {
+        match value {
+            DistributionArg::Histogram => DistributionKind::Histogram,
+            DistributionArg::Cdf => DistributionKind::Cdf,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum SpilloverArg {
+    Threshold,
+    Proportional,
+}
+
+impl From<SpilloverArg> for SpilloverMode {
+    fn from(value: SpilloverArg) -> Self {
+        match value {
+            SpilloverArg::Threshold => SpilloverMode::Threshold,
+            SpilloverArg::Proportional => SpilloverMode::Proportional,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum LatencyBackendArg {
+    Exact,
+    Hdr,
+}
+
+impl From<LatencyBackendArg> for LatencyBackend {
+    fn from(value: LatencyBackendArg) -> Self {
+        match value {
+            LatencyBackendArg::Exact => LatencyBackend::Exact,
+            LatencyBackendArg::Hdr => LatencyBackend::Hdr,
+        }
+    }
 }
 
-impl From<AlgoArg> for AlgoConfig {
-    fn from(value: AlgoArg) -> Self {
+impl From<SessionLengthArg> for SessionLength {
+    fn from(value: SessionLengthArg) -> Self {
         match value {
-            AlgoArg::RoundRobin => AlgoConfig::RoundRobin,
-            AlgoArg::WeightedRoundRobin => AlgoConfig::WeightedRoundRobin,
-            AlgoArg::LeastConnections => AlgoConfig::LeastConnections,
-            AlgoArg::LeastResponseTime => AlgoConfig::LeastResponseTime,
+            SessionLengthArg::Fixed => SessionLength::Fixed,
+            SessionLengthArg::Geometric => SessionLength::Geometric,
         }
     }
 }
 
+#[derive(ValueEnum, Clone, Debug, PartialEq, Eq)]
+pub enum FormatArg {
+    Human,
+    Summary,
+    Json,
+    Markdown,
+    Csv,
+}
+
 pub fn parse_args() -> Result<CliArgs> {
     CliArgs::try_parse().map_err(|e| Error::Cli(e.to_string()))
 }
@@ -132,6 +546,9 @@ pub fn parse_command() -> Result<Command> {
         None => {
             let run_args = RunArgs {
                 algo: args.algo,
+                algo_param: args.algo_param,
+                script: args.script,
+                plugin: args.plugin,
                 servers: args.servers,
                 server: args.server,
                 requests: args.requests,
@@ -143,7 +560,41 @@ pub fn parse_command() -> Result<Command> {
                 summary: args.summary,
                 format: args.format,
                 seed: args.seed,
+                replications: args.replications,
+                event_log: args.event_log,
                 config: args.config,
+                balancers: args.balancers,
+                sync_interval_ms: args.sync_interval_ms,
+                sync_delay_ms: args.sync_delay_ms,
+                network_ms: args.network_ms,
+                network_jitter_ms: args.network_jitter_ms,
+                server_zone: args.server_zone,
+                zone: args.zone,
+                spillover_threshold: args.spillover_threshold,
+                spillover: args.spillover,
+                server_priority: args.server_priority,
+                outage: args.outage,
+                healthy_threshold: args.healthy_threshold,
+                session_length: args.session_length,
+                session_length_dist: args.session_length_dist,
+                sessions: args.sessions,
+                sticky_max_in_flight: args.sticky_max_in_flight,
+                keys: args.keys,
+                key_zipf: args.key_zipf,
+                cache_size: args.cache_size,
+                cache_hit_ms: args.cache_hit_ms,
+                max_in_flight: args.max_in_flight,
+                rate_limit: args.rate_limit,
+                rate_limit_burst: args.rate_limit_burst,
+                adaptive_limit_ms: args.adaptive_limit_ms,
+                warmup_ms: args.warmup_ms,
+                measure_window_ms: args.measure_window_ms,
+                percentiles: args.percentiles,
+                latency_distribution: args.latency_distribution,
+                latency_backend: args.latency_backend,
+                hdr_digits: args.hdr_digits,
+                timeseries: args.timeseries,
+                timeseries_out: args.timeseries_out,
             };
             Ok(Command::Run(run_args))
         }
@@ -179,7 +630,9 @@ pub fn build_config_from_run_args(args: RunArgs) -> Result<(SimConfig, FormatArg
             .algo
             .clone()
             .ok_or_else(|| Error::Cli("missing required --algo".to_string()))?;
-        let servers = parse_server_args(&args.server, args.servers.as_deref())?;
+        let mut servers = parse_server_args(&args.server, args.servers.as_deref())?;
+        apply_network_args(&mut servers, &args);
+        apply_server_attribute_args(&mut servers, &args)?;
         let requests = if args.overload {
             RequestProfile::Poisson {
                 rate: overload_rate(&servers, args.overload_factor),
@@ -209,15 +662,29 @@ pub fn build_config_from_run_args(args: RunArgs) -> Result<(SimConfig, FormatArg
         } else {
             TieBreakConfig::Stable
         };
-        return Ok((
-            create_config(servers, requests, algo, tie_break, args.seed),
-            format,
-        ));
+        let mut config = create_config(servers, requests, algo, tie_break, args.seed);
+        apply_algo_param_args(&mut config.algo, &args)?;
+        config.balancers = balancers_from_args(&args, None)?;
+        config.locality = locality_from_args(&args, None);
+        if let Some(healthy_threshold) = args.healthy_threshold {
+            config.failover = Some(FailoverConfig { healthy_threshold });
+        }
+        config.sessions = sessions_from_args(&args, None);
+        config.keys = keys_from_args(&args, None);
+        config.cache = cache_from_args(&args, None);
+        config.admission = admission_from_args(&args, None)?;
+        config.measurement = measurement_from_args(&args, None);
+        config.metrics = metrics_from_args(&args, None)?;
+        return Ok((config, format));
     };
 
-    if let Some(algo) = args.algo {
-        config.algo = algo.into();
+    if let Some(algo) = args.algo.clone() {
+        // Params in the file belong to the strategy named there.
+        if algo != config.algo.name() {
+            config.algo = AlgoConfig::new(algo);
+        }
     }
+    apply_algo_param_args(&mut config.algo, &args)?;
     if let Some(requests) = args.requests {
         config.requests = RequestProfile::FixedCount(requests);
     }
@@ -237,10 +704,23 @@ pub fn build_config_from_run_args(args: RunArgs) -> Result<(SimConfig, FormatArg
     if !args.server.is_empty() || args.servers.is_some() {
         config.servers = parse_server_args(&args.server, args.servers.as_deref())?;
     }
+    apply_network_args(&mut config.servers, &args);
+    apply_server_attribute_args(&mut config.servers, &args)?;
+    config.locality = locality_from_args(&args, config.locality.take());
+    if let Some(healthy_threshold) = args.healthy_threshold {
+        config.failover = Some(FailoverConfig { healthy_threshold });
+    }
+    config.sessions = sessions_from_args(&args, config.sessions.take());
+    config.keys = keys_from_args(&args, config.keys.take());
+    config.cache = cache_from_args(&args, config.cache.take());
+    config.admission = admission_from_args(&args, config.admission.take())?;
+    config.measurement = measurement_from_args(&args, config.measurement.take());
+    config.metrics = metrics_from_args(&args, config.metrics.take())?;
     if args.seed.is_some() {
         config.seed = args.seed;
         config.tie_break = TieBreakConfig::Seeded;
     }
+    config.balancers = balancers_from_args(&args, config.balancers.take())?;
 
     Ok((config, format))
 }
@@ -339,12 +819,339 @@ fn parse_server_spec(entry: &str) -> Result<ServerConfig> {
     }
 
     Ok(ServerConfig {
-        name: name.to_string(),
-        base_latency_ms: latency_ms,
         weight,
+        ..ServerConfig::new(name, latency_ms)
     })
 }
 
+fn apply_algo_param_args(algo: &mut AlgoConfig, args: &RunArgs) -> Result<()> {
+    for entry in &args.algo_param {
+        let (name, value) = entry
+            .split_once('=')
+            .map(|(name, value)| (name.trim(), value.trim()))
+            .filter(|(name, value)| !name.is_empty() && !value.is_empty())
+            .ok_or_else(|| {
+                Error::Cli(format!(
+                    "invalid --algo-param '{}': expected key=value",
+                    entry
+                ))
+            })?;
+        algo.set_param(name, ParamValue::parse(value));
+    }
+    Ok(())
+}
+
+fn apply_network_args(servers: &mut [ServerConfig], args: &RunArgs) {
+    for server in servers {
+        if let Some(network_ms) = args.network_ms {
+            server.network_latency_ms = network_ms;
+        }
+        if let Some(jitter_ms) = args.network_jitter_ms {
+            server.network_jitter_ms = jitter_ms;
+        }
+    }
+}
+
+fn apply_server_attribute_args(servers: &mut [ServerConfig], args: &RunArgs) -> Result<()> {
+    for entry in &args.server_zone {
+        let (server, zone) = server_attribute(servers, entry, "--server-zone", "zone")?;
+        server.zone = Some(zone.to_string());
+    }
+    for entry in &args.server_priority {
+        let (server, value) = server_attribute(servers, entry, "--server-priority", "priority")?;
+        server.priority = value.parse().map_err(|_| {
+            Error::Cli(format!(
+                "invalid --server-priority '{}': expected name=priority",
+                entry
+            ))
+        })?;
+    }
+    for entry in &args.outage {
+        let (server, value) = server_attribute(servers, entry, "--outage", "start..end")?;
+        let outage = value
+            .split_once("..")
+            .and_then(|(start, end)| {
+                Some(OutageWindow {
+                    start_ms: start.trim().parse().ok()?,
+                    end_ms: end.trim().parse().ok()?,
+                })
+            })
+            .ok_or_else(|| {
+                Error::Cli(format!(
+                    "invalid --outage '{}': expected name=start..end",
+                    entry
+                ))
+            })?;
+        server.outages.push(outage);
+    }
+    Ok(())
+}
+
+fn server_attribute<'a, 'b>(
+    servers: &'a mut [ServerConfig],
+    entry: &'b str,
+    flag: &str,
+    value_name: &str,
+) -> Result<(&'a mut ServerConfig, &'b str)> {
+    let (name, value) = entry
+        .split_once('=')
+        .map(|(name, value)| (name.trim(), value.trim()))
+        .filter(|(name, value)| !name.is_empty() && !value.is_empty())
+        .ok_or_else(|| {
+            Error::Cli(format!(
+                "invalid {} '{}': expected name={}",
+                flag, entry, value_name
+            ))
+        })?;
+    let server = servers
+        .iter_mut()
+        .find(|server| server.name == name)
+        .ok_or_else(|| Error::Cli(format!("{}: unknown server '{}'", flag, name)))?;
+    Ok((server, value))
+}
+
+fn locality_from_args(args: &RunArgs, existing: Option<LocalityConfig>) -> Option<LocalityConfig> {
+    match (args.zone.clone(), existing) {
+        (Some(zone), existing) => Some(LocalityConfig {
+            zone,
+            spillover_threshold: args
+                .spillover_threshold
+                .or(existing
+                    .as_ref()
+                    .map(|locality| locality.spillover_threshold))
+                .unwrap_or_else(default_spillover_threshold),
+            spillover: args
+                .spillover
+                .map(SpilloverMode::from)
+                .or(existing.map(|locality| locality.spillover))
+                .unwrap_or_default(),
+        }),
+        (None, Some(mut locality)) => {
+            if let Some(threshold) = args.spillover_threshold {
+                locality.spillover_threshold = threshold;
+            }
+            if let Some(spillover) = args.spillover {
+                locality.spillover = spillover.into();
+            }
+            Some(locality)
+        }
+        (None, None) => None,
+    }
+}
+
+/// Merges the balancer flags into the config file's balancers; the sync
+/// flags need balancers (and a delay needs an interval) from either source.
+fn balancers_from_args(
+    args: &RunArgs,
+    existing: Option<BalancerConfig>,
+) -> Result<Option<BalancerConfig>> {
+    let mut balancers = match (args.balancers, existing) {
+        (Some(count), Some(balancers)) => Some(BalancerConfig { count, ..balancers }),
+        (Some(count), None) => Some(BalancerConfig {
+            count,
+            sync_interval_ms: None,
+            sync_delay_ms: 0,
+        }),
+        (None, existing) => existing,
+    };
+    if let Some(interval) = args.sync_interval_ms {
+        let Some(balancers) = &mut balancers else {
+            return Err(Error::Cli(
+                "--sync-interval-ms requires --balancers".to_string(),
+            ));
+        };
+        balancers.sync_interval_ms = Some(interval);
+    }
+    if let Some(delay) = args.sync_delay_ms {
+        match &mut balancers {
+            Some(balancers) if balancers.sync_interval_ms.is_some() => {
+                balancers.sync_delay_ms = delay;
+            }
+            _ => {
+                return Err(Error::Cli(
+                    "--sync-delay-ms requires --balancers and --sync-interval-ms".to_string(),
+                ))
+            }
+        }
+    }
+    Ok(balancers)
+}
+
+fn sessions_from_args(args: &RunArgs, existing: Option<SessionConfig>) -> Option<SessionConfig> {
+    let mut sessions = match (args.session_length, existing) {
+        (Some(mean_length), Some(mut sessions)) => {
+            sessions.mean_length = mean_length;
+            sessions
+        }
+        (Some(mean_length), None) => SessionConfig {
+            concurrent: default_concurrent_sessions(),
+            mean_length,
+            length: SessionLength::default(),
+            max_in_flight: None,
+        },
+        (None, Some(sessions)) => sessions,
+        (None, None) => return None,
+    };
+    if let Some(length) = args.session_length_dist {
+        sessions.length = length.into();
+    }
+    if let Some(concurrent) = args.sessions {
+        sessions.concurrent = concurrent;
+    }
+    if args.sticky_max_in_flight.is_some() {
+        sessions.max_in_flight = args.sticky_max_in_flight;
+    }
+    Some(sessions)
+}
+
+fn keys_from_args(args: &RunArgs, existing: Option<KeyConfig>) -> Option<KeyConfig> {
+    let mut keys = match (args.keys, existing) {
+        (Some(count), Some(mut keys)) => {
+            keys.count = count;
+            keys
+        }
+        (Some(count), None) => KeyConfig {
+            count,
+            zipf_exponent: 0.0,
+        },
+        (None, Some(keys)) => keys,
+        (None, None) => return None,
+    };
+    if let Some(exponent) = args.key_zipf {
+        keys.zipf_exponent = exponent;
+    }
+    Some(keys)
+}
+
+fn cache_from_args(args: &RunArgs, existing: Option<CacheConfig>) -> Option<CacheConfig> {
+    let mut cache = match (args.cache_size, existing) {
+        (Some(capacity), Some(mut cache)) => {
+            cache.capacity = capacity;
+            cache
+        }
+        (Some(capacity), None) => CacheConfig {
+            capacity,
+            hit_latency_ms: default_cache_hit_latency_ms(),
+        },
+        (None, Some(cache)) => cache,
+        (None, None) => return None,
+    };
+    if let Some(hit_latency_ms) = args.cache_hit_ms {
+        cache.hit_latency_ms = hit_latency_ms;
+    }
+    Some(cache)
+}
+
+fn measurement_from_args(
+    args: &RunArgs,
+    existing: Option<MeasurementConfig>,
+) -> Option<MeasurementConfig> {
+    if args.warmup_ms.is_none() && args.measure_window_ms.is_none() {
+        return existing;
+    }
+    let mut measurement = existing.unwrap_or(MeasurementConfig {
+        warmup_ms: 0,
+        window_ms: None,
+    });
+    if let Some(warmup_ms) = args.warmup_ms {
+        measurement.warmup_ms = warmup_ms;
+    }
+    if let Some(window_ms) = args.measure_window_ms {
+        measurement.window_ms = Some(window_ms);
+    }
+    Some(measurement)
+}
+
+fn metrics_from_args(
+    args: &RunArgs,
+    existing: Option<MetricsConfig>,
+) -> Result<Option<MetricsConfig>> {
+    if args.percentiles.is_empty()
+        && args.latency_distribution.is_none()
+        && args.latency_backend.is_none()
+        && args.hdr_digits.is_none()
+        && args.timeseries.is_none()
+    {
+        return Ok(existing);
+    }
+    let mut metrics = existing.unwrap_or_default();
+    if !args.percentiles.is_empty() {
+        metrics.percentiles = args
+            .percentiles
+            .iter()
+            .map(|entry| Percentile::try_from(ParamValue::parse(entry.trim())).map_err(Error::Cli))
+            .collect::<Result<_>>()?;
+    }
+    if let Some(distribution) = args.latency_distribution {
+        metrics.distribution = Some(distribution.into());
+    }
+    if let Some(backend) = args.latency_backend {
+        metrics.backend = backend.into();
+    }
+    if let Some(digits) = args.hdr_digits {
+        metrics.significant_digits = digits;
+    }
+    if let Some(timeseries) = &args.timeseries {
+        metrics.timeseries = Some(
+            TimeSeriesSampling::try_from(ParamValue::parse(timeseries.trim()))
+                .map_err(Error::Cli)?,
+        );
+    }
+    Ok(Some(metrics))
+}
+
+fn admission_from_args(
+    args: &RunArgs,
+    existing: Option<AdmissionConfig>,
+) -> Result<Option<AdmissionConfig>> {
+    if args.rate_limit_burst.is_some() && args.rate_limit.is_none() {
+        let configured = existing
+            .as_ref()
+            .is_some_and(|admission| admission.rate_limit.is_some());
+        if !configured {
+            return Err(Error::Cli(
+                "--rate-limit-burst requires --rate-limit".to_string(),
+            ));
+        }
+    }
+    let requested = args.max_in_flight.is_some()
+        || args.rate_limit.is_some()
+        || args.rate_limit_burst.is_some()
+        || args.adaptive_limit_ms.is_some();
+    if !requested {
+        return Ok(existing);
+    }
+    let mut admission = existing.unwrap_or(AdmissionConfig {
+        max_in_flight: None,
+        rate_limit: None,
+        adaptive: None,
+    });
+    if args.max_in_flight.is_some() {
+        admission.max_in_flight = args.max_in_flight;
+    }
+    if let Some(rate) = args.rate_limit {
+        match &mut admission.rate_limit {
+            Some(rate_limit) => rate_limit.rate = rate,
+            None => {
+                admission.rate_limit = Some(TokenBucketConfig {
+                    rate,
+                    burst: default_rate_limit_burst(),
+                })
+            }
+        }
+    }
+    if let (Some(burst), Some(rate_limit)) = (args.rate_limit_burst, &mut admission.rate_limit) {
+        rate_limit.burst = burst;
+    }
+    if let Some(threshold) = args.adaptive_limit_ms {
+        match &mut admission.adaptive {
+            Some(adaptive) => adaptive.latency_threshold_ms = threshold,
+            None => admission.adaptive = Some(AdaptiveLimitConfig::with_threshold(threshold)),
+        }
+    }
+    Ok(Some(admission))
+}
+
 fn normalized_server_entry(entry: &str) -> Result<&str> {
     let trimmed = entry.trim();
     if trimmed.is_empty() {
@@ -356,16 +1163,14 @@ fn normalized_server_entry(entry: &str) -> Result<&str> {
 fn create_config(
     servers: Vec<ServerConfig>,
     requests: RequestProfile,
-    algo: AlgoArg,
+    algo: String,
     tie_break: TieBreakConfig,
     seed: Option<u64>,
 ) -> SimConfig {
     SimConfig {
-        servers,
-        requests,
-        algo: algo.into(),
         tie_break,
         seed,
+        ..SimConfig::new(servers, requests, AlgoConfig::new(algo))
     }
 }
 
@@ -399,19 +1204,144 @@ pub fn format_config(config: &SimConfig) -> String {
         format!("Algorithm: {}", algo_label),
         requests_label,
         format!("Tie-break: {}", tie_break_label),
-        "Servers:".to_string(),
     ];
+    if let Some(balancers) = &config.balancers {
+        lines.push(format!("Balancers: {}", balancers.label()));
+    }
+    if let Some(locality) = &config.locality {
+        lines.push(format!(
+            "Locality: zone {} ({} spillover at {}% busy)",
+            locality.zone,
+            locality.spillover,
+            locality.spillover_threshold * 100.0
+        ));
+    }
+    if let Some(sessions) = &config.sessions {
+        let limit_label = match sessions.max_in_flight {
+            Some(limit) => format!(", break at {} in flight", limit),
+            None => String::new(),
+        };
+        lines.push(format!(
+            "Sessions: {} concurrent, {} length (mean {}){}",
+            sessions.concurrent, sessions.length, sessions.mean_length, limit_label
+        ));
+    }
+    if let Some(keys) = &config.keys {
+        let popularity_label = if keys.zipf_exponent > 0.0 {
+            format!("zipf({})", keys.zipf_exponent)
+        } else {
+            "uniform".to_string()
+        };
+        lines.push(format!("Keys: {} ({})", keys.count, popularity_label));
+    }
+    if let Some(cache) = &config.cache {
+        lines.push(format!(
+            "Cache: {} keys per server, hit latency {}ms",
+            cache.capacity, cache.hit_latency_ms
+        ));
+    }
+    if let Some(admission) = &config.admission {
+        let mut limits = Vec::new();
+        if let Some(limit) = admission.max_in_flight {
+            limits.push(format!("max {} in flight", limit));
+        }
+        if let Some(rate_limit) = &admission.rate_limit {
+            limits.push(format!(
+                "rate {}/s (burst {})",
+                rate_limit.rate, rate_limit.burst
+            ));
+        }
+        if let Some(adaptive) = &admission.adaptive {
+            limits.push(format!(
+                "adaptive limit {}..{} backing off above {}ms",
+                adaptive.min_limit, adaptive.max_limit, adaptive.latency_threshold_ms
+            ));
+        }
+        lines.push(format!("Admission: {}", limits.join(", ")));
+    }
+    if let Some(measurement) = &config.measurement {
+        lines.push(format!("Measurement: {}", measurement.label()));
+    }
+    if let Some(metrics) = &config.metrics {
+        let percentiles = metrics
+            .percentiles
+            .iter()
+            .map(Percentile::to_string)
+            .collect::<Vec<_>>();
+        let distribution_label = match metrics.distribution {
+            Some(kind) => format!(", {} of response times", kind),
+            None => String::new(),
+        };
+        let backend_label = match metrics.backend {
+            LatencyBackend::Exact => String::new(),
+            LatencyBackend::Hdr => {
+                format!(" (hdr, {} significant digits)", metrics.significant_digits)
+            }
+        };
+        let timeseries_label = match &metrics.timeseries {
+            Some(sampling) => format!(", server time series {}", sampling.label()),
+            None => String::new(),
+        };
+        lines.push(format!(
+            "Metrics: {}{}{}{}",
+            percentiles.join(", "),
+            distribution_label,
+            backend_label,
+            timeseries_label
+        ));
+    }
+    if let Some(failover) = &config.failover {
+        lines.push(format!(
+            "Failover: tier needs {}% healthy",
+            round_percent(failover.healthy_threshold)
+        ));
+    }
+    lines.push("Servers:".to_string());
 
     for server in &config.servers {
+        let network_label = match (server.network_latency_ms, server.network_jitter_ms) {
+            (0, 0) => String::new(),
+            (latency, 0) => format!(", network: {}ms", latency),
+            (latency, jitter) => format!(", network: {}ms +0..{}ms", latency, jitter),
+        };
+        let zone_label = match &server.zone {
+            Some(zone) => format!(", zone: {}", zone),
+            None => String::new(),
+        };
+        let priority_label = if server.priority > 0 {
+            format!(", priority: {}", server.priority)
+        } else {
+            String::new()
+        };
+        let outage_label = if server.outages.is_empty() {
+            String::new()
+        } else {
+            let windows = server
+                .outages
+                .iter()
+                .map(|outage| format!("{}..{}ms", outage.start_ms, outage.end_ms))
+                .collect::<Vec<_>>();
+            format!(", outages: {}", windows.join(" "))
+        };
         lines.push(format!(
-            "- {} (latency: {}ms, weight: {})",
-            server.name, server.base_latency_ms, server.weight
+            "- {} (latency: {}ms, weight: {}{}{}{}{})",
+            server.name,
+            server.base_latency_ms,
+            server.weight,
+            network_label,
+            zone_label,
+            priority_label,
+            outage_label
         ));
     }
 
     lines.join("\n") + "\n"
 }
 
+fn round_percent(fraction: f64) -> f64 {
+    (fraction * 10_000.0).round() / 100.0
+}
+
 fn capacity_rps(servers: &[ServerConfig]) -> f64 {
     servers
         .iter()
@@ -419,7 +1349,8 @@ fn capacity_rps(servers: &[ServerConfig]) -> f64 {
         .sum()
 }
 
-fn overload_rate(servers: &[ServerConfig], overload_factor: f64) -> f64 {
+/// Poisson arrival rate at `overload_factor` times the servers' total capacity.
+pub fn overload_rate(servers: &[ServerConfig], overload_factor: f64) -> f64 {
     capacity_rps(servers) * overload_factor
 }
 
@@ -1,26 +1,50 @@
 use rand::rngs::StdRng;
 use rand::{Rng, RngCore, SeedableRng};
 use std::cmp::Reverse;
-use std::collections::{BinaryHeap, HashSet};
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 
-use crate::algorithms::{build_strategy, SelectionContext, SelectionStrategy};
+use crate::admission::AdmissionController;
+use crate::algorithms::{
+    build_routing_strategy, SelectionContext, SelectionStrategy, StrategyRegistry,
+};
+use crate::arrivals::Arrivals;
+use crate::cache::LruCache;
 use crate::error::{Error, Result};
-use crate::events::{Event, Request, ScheduledEvent};
-use crate::models::{RequestProfile, ServerConfig, SimConfig, TieBreakConfig};
+use crate::event_log::{EventKind, EventLog, EventRecord};
+use crate::events::{Event, ScheduledEvent};
+use crate::latency::LatencyRecorder;
+use crate::models::{
+    AdmissionConfig, BalancerConfig, MetricsConfig, RequestProfile, ServerConfig, SimConfig,
+    TieBreakConfig,
+};
 use crate::state::{
-    Assignment, EngineState, Phase1Metrics, ResponseTimePercentiles, RunMetadata, ServerId,
-    ServerState, ServerSummary, ServerUtilization, SimulationResult,
+    AdmissionMetrics, Assignment, CacheMetrics, EngineState, FailureMetrics, KeyMetrics,
+    Phase1Metrics, RejectReason, Rejection, ResponseTimePercentiles, RunMetadata, ServerCacheStats,
+    ServerId, ServerState, ServerSummary, ServerUtilization, SessionMetrics, SimulationResult,
 };
+use crate::timeseries::TimeSeriesRecorder;
+
+/// Mixed into the seed for network jitter so hop delays are not correlated
+/// with inter-arrival gaps drawn from the run seed.
+const NETWORK_STREAM: u64 = 0x6e65_7477;
 
 pub struct SimulationEngine {
     pub config: SimConfig,
     pub state: EngineState,
-    pub strategy: Box<dyn SelectionStrategy>,
+    pub strategies: Vec<Box<dyn SelectionStrategy>>,
     pub rng: StdRng,
+    /// Receives every processed event while the engine runs.
+    pub event_log: Option<EventLog>,
+    /// Most events queued at once during the last run. Arrivals are queued
+    /// one at a time, so this tracks in-flight work, not the request count.
+    pub(crate) peak_queued_events: usize,
 }
 
 impl SimulationEngine {
-    pub fn new(config: SimConfig, strategy: Box<dyn SelectionStrategy>) -> Self {
+    pub fn new(
+        config: SimConfig,
+        mut build_strategy: impl FnMut() -> Box<dyn SelectionStrategy>,
+    ) -> Self {
         let seed = match config.tie_break {
             TieBreakConfig::Seeded => config.seed.unwrap_or(0),
             TieBreakConfig::Stable => 0,
@@ -29,84 +53,426 @@ impl SimulationEngine {
         let state = EngineState {
             time_ms: 0,
             servers: Vec::new(),
+            balancer_views: Vec::new(),
             assignments: Vec::new(),
         };
+        let strategies = (0..balancer_count(&config))
+            .map(|_| build_strategy())
+            .collect();
 
         Self {
             config,
             state,
-            strategy,
+            strategies,
             rng,
+            event_log: None,
+            peak_queued_events: 0,
         }
     }
 
+    pub fn with_event_log(mut self, event_log: EventLog) -> Self {
+        self.event_log = Some(event_log);
+        self
+    }
+
     pub fn run(&mut self, store_assignments: bool) -> Result<SimulationResult> {
         validate_config(&self.config)?;
-        let requests = build_requests(&self.config.requests, self.config.seed)?;
+        let mut arrivals = Arrivals::new(&self.config);
+        let first_arrival = arrivals.next().ok_or(Error::RequestsZero)?;
+        let mut network_rng = StdRng::seed_from_u64(self.config.seed.unwrap_or(0) ^ NETWORK_STREAM);
 
         self.state.servers = init_server_state(&self.config.servers);
-        if store_assignments {
-            self.state.assignments = Vec::with_capacity(requests.len());
-        } else {
-            self.state.assignments = Vec::new();
-        }
+        self.state.balancer_views = vec![self.state.servers.clone(); self.strategies.len()];
+        self.state.assignments = Vec::new();
 
-        let mut counts = vec![0u32; self.state.servers.len()];
+        let balancers = self.strategies.len();
+        let (sync_interval_ms, sync_delay_ms) = match &self.config.balancers {
+            Some(config) => (config.sync_interval_ms, config.sync_delay_ms),
+            None => (None, 0),
+        };
+        let mut pending_snapshots: VecDeque<Vec<ServerState>> = VecDeque::new();
+        // Arrivals queued or in flight; the next arrival is queued as soon as
+        // the previous one is processed, so this is 0 only once all are done.
+        let mut pending_requests = 1usize;
+
+        let mut measured_counts = vec![0u32; self.state.servers.len()];
         let mut total_response_ms = vec![0u64; self.state.servers.len()];
         let mut total_service_ms = vec![0u64; self.state.servers.len()];
-        let mut response_times = Vec::with_capacity(requests.len());
+        let metrics_config = self.config.metrics.clone().unwrap_or_default();
+        let mut response_times = LatencyRecorder::new(&metrics_config);
+        let mut tallies: Vec<ServerTally> = (0..self.state.servers.len())
+            .map(|_| ServerTally::new(&metrics_config))
+            .collect();
         let mut total_wait_ms = 0u64;
+        let mut total_network_ms = 0u64;
+        // Open sessions only: entries are dropped when a session ends.
+        let mut session_servers: HashMap<u64, ServerId> = HashMap::new();
+        let mut session_count = 0usize;
+        let mut stickiness_breaks = 0u64;
+        // Bounded by the key space, not the request count.
+        let mut key_servers: HashMap<u64, ServerId> = HashMap::new();
+        let mut repeated_keys = 0u64;
+        let mut key_hits = 0u64;
+        let mut caches = match &self.config.cache {
+            Some(cache) => vec![LruCache::new(cache.capacity); self.state.servers.len()],
+            None => Vec::new(),
+        };
+        let mut cache_lookups = vec![0u64; caches.len()];
+        let mut cache_hits = vec![0u64; caches.len()];
+        let mut admission = self.config.admission.as_ref().map(AdmissionController::new);
+        let mut rejected = [0u64; 3];
+        // Dispatched requests not yet complete, by request id. Their metrics
+        // are recorded on completion, or dropped if their server goes down.
+        let mut in_flight: HashMap<usize, InFlightRequest> = HashMap::new();
+        let mut failed_in_flight = 0u64;
+        let mut dropped_no_server = 0u64;
+        let mut rejections = Vec::new();
         let mut duration_ms = 0;
-        let mut event_queue = schedule_requests(requests);
-        let first_arrival_ms = event_queue.peek().map(|Reverse(event)| event.time_ms);
+        let mut event_queue = BinaryHeap::new();
+        event_queue.push(Reverse(ScheduledEvent::new(
+            first_arrival.arrival_time_ms,
+            Event::RequestArrival(first_arrival),
+        )));
+        let (window_start_ms, window_end_ms) = measurement_window(&self.config);
+        if let Some(interval) = sync_interval_ms {
+            event_queue.push(Reverse(ScheduledEvent::new(
+                interval,
+                Event::SyncTick { tick: 1 },
+            )));
+        }
+        schedule_outages(&mut event_queue, &self.config.servers);
 
         let mut stable_rng = StableRng;
+        let mut timeseries = metrics_config
+            .timeseries
+            .map(|sampling| TimeSeriesRecorder::new(sampling, &self.state.servers));
 
+        self.peak_queued_events = 0;
         while let Some(Reverse(next_event)) = event_queue.pop() {
+            self.peak_queued_events = self.peak_queued_events.max(event_queue.len() + 1);
+            if let Some(timeseries) = &mut timeseries {
+                timeseries.advance(next_event.time_ms, &self.state.servers);
+                timeseries.on_event(next_event.time_ms);
+            }
             self.state.time_ms = next_event.time_ms;
             match next_event.event {
-                Event::RequestComplete { server_id, .. } => {
+                Event::RequestComplete {
+                    server_id,
+                    request_id,
+                    balancer_id,
+                    latency_ms,
+                } => {
+                    // Requests failed by an outage have already been settled.
+                    let Some(request) = in_flight.remove(&request_id) else {
+                        continue;
+                    };
+                    if request.measured {
+                        let server_index = request.server_index;
+                        measured_counts[server_index] += 1;
+                        total_response_ms[server_index] += request.response_time;
+                        total_service_ms[server_index] += request.service_time;
+                        response_times.record(request.response_time);
+                        tallies[server_index].record(
+                            request.response_time,
+                            request.wait_time,
+                            request.queue_depth,
+                        );
+                        total_wait_ms += request.wait_time;
+                        total_network_ms += request.network_ms;
+                        duration_ms = duration_ms.max(request.completed_at);
+                    }
+                    if let Some(controller) = &mut admission {
+                        controller.on_complete(latency_ms);
+                    }
                     let server_idx = usize::from(server_id);
+                    if let Some(log) = &mut self.event_log {
+                        log.write(&EventRecord {
+                            request_id: Some(request_id),
+                            balancer_id: Some(balancer_id),
+                            server_id: Some(server_idx),
+                            server: Some(&self.config.servers[server_idx].name),
+                            latency_ms: Some(latency_ms),
+                            ..EventRecord::new(self.state.time_ms, EventKind::RequestComplete)
+                        })?;
+                    }
                     let server = &mut self.state.servers[server_idx];
                     server.active_connections -= 1;
                     server.in_flight -= 1;
+                    let view = &mut self.state.balancer_views[balancer_id][server_idx];
+                    view.active_connections = view.active_connections.saturating_sub(1);
+                    view.in_flight = view.in_flight.saturating_sub(1);
+                    self.strategies[balancer_id].on_complete(view);
+                    pending_requests -= 1;
+                }
+                Event::ServerDown { server_id } => {
+                    self.log_server_event(EventKind::ServerDown, server_id)?;
+                    self.set_server_health(server_id, false);
+                    let server_idx = usize::from(server_id);
+                    let now = self.state.time_ms;
+                    // Work queued or in service on the server is lost; responses
+                    // already on their way back still arrive.
+                    let mut failed_ids = in_flight
+                        .iter()
+                        .filter(|(_, request)| {
+                            request.server_index == server_idx && request.service_completed_at > now
+                        })
+                        .map(|(&request_id, _)| request_id)
+                        .collect::<Vec<_>>();
+                    failed_ids.sort_unstable();
+                    for request_id in failed_ids {
+                        let Some(request) = in_flight.remove(&request_id) else {
+                            continue;
+                        };
+                        if request.measured {
+                            failed_in_flight += 1;
+                        }
+                        if let Some(controller) = &mut admission {
+                            controller.on_failure();
+                        }
+                        if let Some(log) = &mut self.event_log {
+                            log.write(&EventRecord {
+                                request_id: Some(request_id),
+                                balancer_id: Some(request.balancer_id),
+                                server_id: Some(server_idx),
+                                server: Some(&self.config.servers[server_idx].name),
+                                ..EventRecord::new(now, EventKind::RequestFailed)
+                            })?;
+                        }
+                        let server = &mut self.state.servers[server_idx];
+                        server.active_connections -= 1;
+                        server.in_flight -= 1;
+                        let view = &mut self.state.balancer_views[request.balancer_id][server_idx];
+                        view.active_connections = view.active_connections.saturating_sub(1);
+                        view.in_flight = view.in_flight.saturating_sub(1);
+                        self.strategies[request.balancer_id].on_complete(view);
+                        pending_requests -= 1;
+                    }
+                    self.state.servers[server_idx].next_available_ms = now;
+                    for view in &mut self.state.balancer_views {
+                        view[server_idx].next_available_ms =
+                            view[server_idx].next_available_ms.min(now);
+                    }
+                    tallies[server_idx].clear_queue();
+                    if let Some(timeseries) = &mut timeseries {
+                        timeseries.on_server_down(server_idx, now);
+                    }
+                }
+                Event::ServerUp { server_id } => {
+                    self.log_server_event(EventKind::ServerUp, server_id)?;
+                    self.set_server_health(server_id, true);
+                }
+                Event::SyncTick { tick } => {
+                    if let Some(log) = &mut self.event_log {
+                        log.write(&EventRecord {
+                            tick: Some(tick),
+                            ..EventRecord::new(self.state.time_ms, EventKind::SyncTick)
+                        })?;
+                    }
+                    pending_snapshots.push_back(self.state.servers.clone());
+                    event_queue.push(Reverse(ScheduledEvent::new(
+                        self.state.time_ms + sync_delay_ms,
+                        Event::ViewSync { snapshot_id: tick },
+                    )));
+                    if pending_requests > 0 {
+                        if let Some(interval) = sync_interval_ms {
+                            event_queue.push(Reverse(ScheduledEvent::new(
+                                self.state.time_ms + interval,
+                                Event::SyncTick { tick: tick + 1 },
+                            )));
+                        }
+                    }
+                }
+                Event::ViewSync { snapshot_id } => {
+                    if let Some(log) = &mut self.event_log {
+                        log.write(&EventRecord {
+                            tick: Some(snapshot_id),
+                            ..EventRecord::new(self.state.time_ms, EventKind::ViewSync)
+                        })?;
+                    }
+                    if let Some(snapshot) = pending_snapshots.pop_front() {
+                        for view in &mut self.state.balancer_views {
+                            view.clone_from(&snapshot);
+                        }
+                    }
                 }
                 Event::RequestArrival(request) => {
+                    if let Some(next) = arrivals.next() {
+                        pending_requests += 1;
+                        event_queue.push(Reverse(ScheduledEvent::new(
+                            next.arrival_time_ms,
+                            Event::RequestArrival(next),
+                        )));
+                    }
+                    if !self.state.servers.iter().any(|server| server.healthy) {
+                        // Every server is down: the request fails instead of
+                        // being served by a dead server.
+                        if is_measured(request.arrival_time_ms, window_start_ms, window_end_ms) {
+                            dropped_no_server += 1;
+                        }
+                        pending_requests -= 1;
+                        if let Some(log) = &mut self.event_log {
+                            log.write(&EventRecord {
+                                request_id: Some(request.id),
+                                session_id: request.session_id,
+                                key: request.key,
+                                ..EventRecord::new(self.state.time_ms, EventKind::RequestFailed)
+                            })?;
+                        }
+                        continue;
+                    }
+                    // A session always goes through the same balancer, which
+                    // holds its pin.
+                    let balancer_id = match request.session_id {
+                        Some(session_id) => (session_id % balancers as u64) as usize,
+                        None => request.id % balancers,
+                    };
+                    if let Some(controller) = &mut admission {
+                        if let Err(reason) = controller.admit(self.state.time_ms) {
+                            if is_measured(request.arrival_time_ms, window_start_ms, window_end_ms)
+                            {
+                                rejected[reason as usize] += 1;
+                            }
+                            pending_requests -= 1;
+                            if let Some(log) = &mut self.event_log {
+                                log.write(&EventRecord {
+                                    request_id: Some(request.id),
+                                    balancer_id: Some(balancer_id),
+                                    session_id: request.session_id,
+                                    key: request.key,
+                                    reason: Some(reason),
+                                    ..EventRecord::new(
+                                        self.state.time_ms,
+                                        EventKind::RequestRejected,
+                                    )
+                                })?;
+                            }
+                            if store_assignments {
+                                rejections.push(Rejection {
+                                    request_id: request.id,
+                                    arrival_time_ms: request.arrival_time_ms,
+                                    reason,
+                                });
+                            }
+                            continue;
+                        }
+                    }
                     let rng: &mut dyn RngCore = match self.config.tie_break {
                         TieBreakConfig::Stable => &mut stable_rng,
                         TieBreakConfig::Seeded => &mut self.rng,
                     };
                     let mut ctx = SelectionContext {
-                        servers: &self.state.servers,
+                        servers: &self.state.balancer_views[balancer_id],
                         time_ms: self.state.time_ms,
                         rng,
+                        session_id: request.session_id,
+                        request_key: request.key,
+                        tie_break: self.config.tie_break,
                     };
-                    let selection = self.strategy.select(&mut ctx);
+                    let selection = self.strategies[balancer_id].select(&mut ctx);
+                    if let Some(message) = self.strategies[balancer_id].take_error() {
+                        return Err(Error::StrategyFailed(message));
+                    }
                     let server_idx = selection.server_id;
 
+                    if let Some(session_id) = request.session_id {
+                        match session_servers.insert(session_id, server_idx) {
+                            Some(previous) if previous != server_idx => stickiness_breaks += 1,
+                            Some(_) => {}
+                            None => session_count += 1,
+                        }
+                        if request.session_end {
+                            session_servers.remove(&session_id);
+                            self.strategies[balancer_id].on_session_end(session_id);
+                        }
+                    }
+
+                    if let Some(key) = request.key {
+                        if let Some(previous) = key_servers.insert(key, server_idx) {
+                            repeated_keys += 1;
+                            if previous == server_idx {
+                                key_hits += 1;
+                            }
+                        }
+                    }
+
                     let server_index = usize::from(server_idx);
+                    let view = &mut self.state.balancer_views[balancer_id][server_index];
+                    view.active_connections += 1;
+                    view.pick_count += 1;
+                    view.in_flight += 1;
+                    view.next_available_ms = (self.state.time_ms + view.network_latency_ms)
+                        .max(view.next_available_ms)
+                        + view.base_latency_ms;
+
                     let server = &mut self.state.servers[server_index];
                     server.active_connections += 1;
                     server.pick_count += 1;
                     server.in_flight += 1;
 
-                    let started_at = self.state.time_ms.max(server.next_available_ms);
-                    let completed_at = started_at + server.base_latency_ms;
-                    server.next_available_ms = completed_at;
+                    let jitter_ms = self.config.servers[server_index].network_jitter_ms;
+                    let dispatch_ms = network_hop_ms(&mut network_rng, server, jitter_ms);
+                    let return_ms = network_hop_ms(&mut network_rng, server, jitter_ms);
+                    let reached_server_at = self.state.time_ms + dispatch_ms;
+                    let started_at = reached_server_at.max(server.next_available_ms);
+                    let mut service_ms = server.base_latency_ms;
+                    if let (Some(cache), Some(key)) = (&self.config.cache, request.key) {
+                        cache_lookups[server_index] += 1;
+                        if caches[server_index].access(key) {
+                            cache_hits[server_index] += 1;
+                            service_ms = service_ms.min(cache.hit_latency_ms);
+                        }
+                    }
+                    let service_completed_at = started_at + service_ms;
+                    server.next_available_ms = service_completed_at;
+                    if let Some(timeseries) = &mut timeseries {
+                        timeseries.on_service(server_index, started_at, service_completed_at);
+                    }
+                    let completed_at = service_completed_at + return_ms;
+                    let network_ms = dispatch_ms + return_ms;
                     let response_time = completed_at - request.arrival_time_ms;
-                    let service_time = completed_at - started_at;
-                    let wait_time = started_at.saturating_sub(request.arrival_time_ms);
-                    counts[server_index] += 1;
-                    total_response_ms[server_index] += response_time;
-                    total_service_ms[server_index] += service_time;
-                    response_times.push(response_time);
-                    total_wait_ms += wait_time;
-                    duration_ms = duration_ms.max(completed_at);
+                    let service_time = service_completed_at - started_at;
+                    let wait_time = started_at.saturating_sub(reached_server_at);
+                    let queue_depth = tallies[server_index].enqueue(reached_server_at, started_at);
+                    in_flight.insert(
+                        request.id,
+                        InFlightRequest {
+                            server_index,
+                            balancer_id,
+                            service_completed_at,
+                            completed_at,
+                            measured: is_measured(
+                                request.arrival_time_ms,
+                                window_start_ms,
+                                window_end_ms,
+                            ),
+                            response_time,
+                            service_time,
+                            wait_time,
+                            queue_depth,
+                            network_ms,
+                        },
+                    );
+                    if let Some(log) = &mut self.event_log {
+                        log.write(&EventRecord {
+                            request_id: Some(request.id),
+                            balancer_id: Some(balancer_id),
+                            server_id: Some(server_index),
+                            server: Some(&self.config.servers[server_index].name),
+                            score: selection.score,
+                            session_id: request.session_id,
+                            key: request.key,
+                            started_at: Some(started_at),
+                            completed_at: Some(completed_at),
+                            ..EventRecord::new(self.state.time_ms, EventKind::RequestArrival)
+                        })?;
+                    }
                     event_queue.push(Reverse(ScheduledEvent::new(
                         completed_at,
                         Event::RequestComplete {
                             server_id: server_idx,
                             request_id: request.id,
+                            balancer_id,
+                            latency_ms: response_time,
                         },
                     )));
 
@@ -117,6 +483,7 @@ impl SimulationEngine {
                             arrival_time_ms: request.arrival_time_ms,
                             started_at,
                             completed_at,
+                            network_ms,
                             score: selection.score,
                         });
                     }
@@ -124,17 +491,31 @@ impl SimulationEngine {
             }
         }
 
-        let totals = build_server_summaries(&self.state.servers, &counts, &total_response_ms);
+        if let Some(log) = &mut self.event_log {
+            log.flush()?;
+        }
+        let timeseries =
+            timeseries.map(|timeseries| timeseries.finish(self.state.time_ms, &self.state.servers));
+        let totals = build_server_summaries(
+            &self.state.servers,
+            &measured_counts,
+            &total_response_ms,
+            &total_service_ms,
+            tallies,
+        );
 
-        response_times.sort_unstable();
-        let p95_ms = nearest_rank_percentile(&response_times, 95.0);
-        let p99_ms = nearest_rank_percentile(&response_times, 99.0);
-        let active_duration_ms = match self.config.requests {
-            RequestProfile::Burst { at_ms, .. } if at_ms > 0 => {
-                duration_ms.saturating_sub(first_arrival_ms.unwrap_or(0))
-            }
-            _ => duration_ms,
-        };
+        response_times.finish();
+        let response_time = ResponseTimePercentiles::new(
+            metrics_config
+                .percentiles
+                .iter()
+                .map(|&percentile| (percentile, response_times.percentile(percentile.value())))
+                .collect(),
+        );
+        let response_time_distribution = metrics_config
+            .distribution
+            .map(|kind| response_times.distribution(kind));
+        let active_duration_ms = duration_ms.saturating_sub(window_start_ms);
 
         let per_server_utilization = self
             .state
@@ -154,19 +535,96 @@ impl SimulationEngine {
                 }
             })
             .collect::<Vec<_>>();
-        let total_requests = counts.iter().copied().map(u64::from).sum::<u64>();
+        let measured_requests = measured_counts.iter().copied().map(u64::from).sum::<u64>();
         let throughput_rps = if active_duration_ms == 0 {
             0.0
         } else {
-            (total_requests as f64 / active_duration_ms as f64) * 1000.0
-        };
-        let avg_wait_ms = if total_requests == 0 {
-            0
-        } else {
-            total_wait_ms / total_requests
+            (measured_requests as f64 / active_duration_ms as f64) * 1000.0
         };
-        let sum = counts.iter().copied().map(f64::from).sum::<f64>();
-        let sum_sq = counts
+        let avg_wait_ms = total_wait_ms.checked_div(measured_requests).unwrap_or(0);
+        let avg_network_ms = total_network_ms.checked_div(measured_requests).unwrap_or(0);
+        let cross_zone_fraction = self.config.locality.as_ref().map(|locality| {
+            let cross_zone = self
+                .config
+                .servers
+                .iter()
+                .zip(&measured_counts)
+                .filter(|(server, _)| server.zone.as_ref() != Some(&locality.zone))
+                .map(|(_, &count)| u64::from(count))
+                .sum::<u64>();
+            round_to(ratio(cross_zone, measured_requests), 4)
+        });
+        let sessions = self.config.sessions.as_ref().map(|_| {
+            let max_count = measured_counts.iter().copied().max().unwrap_or(0);
+            let load_imbalance = if measured_requests == 0 {
+                0.0
+            } else {
+                max_count as f64 * measured_counts.len() as f64 / measured_requests as f64
+            };
+            SessionMetrics {
+                sessions: session_count,
+                stickiness_breaks,
+                load_imbalance: round_to(load_imbalance, 4),
+            }
+        });
+        let keys = self.config.keys.as_ref().map(|_| KeyMetrics {
+            distinct_keys: key_servers.len(),
+            key_locality: round_to(ratio(key_hits, repeated_keys), 4),
+        });
+        let cache = self.config.cache.as_ref().map(|_| {
+            let per_server = self
+                .state
+                .servers
+                .iter()
+                .enumerate()
+                .map(|(idx, server)| ServerCacheStats {
+                    name: server.name.clone(),
+                    lookups: cache_lookups[idx],
+                    hits: cache_hits[idx],
+                    hit_ratio: round_to(ratio(cache_hits[idx], cache_lookups[idx]), 4),
+                })
+                .collect();
+            CacheMetrics {
+                hit_ratio: round_to(
+                    ratio(cache_hits.iter().sum(), cache_lookups.iter().sum()),
+                    4,
+                ),
+                per_server,
+            }
+        });
+        let has_outages = self
+            .config
+            .servers
+            .iter()
+            .any(|server| !server.outages.is_empty());
+        let failures = has_outages.then(|| {
+            let failed = failed_in_flight + dropped_no_server;
+            FailureMetrics {
+                failed,
+                failed_in_flight,
+                dropped_no_server,
+                failed_fraction: round_to(ratio(failed, measured_requests + failed), 4),
+            }
+        });
+        let admission = self.config.admission.as_ref().map(|_| {
+            // Admitted requests lost to an outage were still admitted.
+            let admitted = measured_requests + failed_in_flight;
+            let total_rejected = rejected.iter().sum::<u64>();
+            AdmissionMetrics {
+                admitted,
+                rejected: total_rejected,
+                rejected_fraction: round_to(ratio(total_rejected, admitted + total_rejected), 4),
+                rejected_max_in_flight: rejected[RejectReason::MaxInFlight as usize],
+                rejected_rate_limit: rejected[RejectReason::RateLimit as usize],
+                rejected_concurrency_limit: rejected[RejectReason::ConcurrencyLimit as usize],
+                final_concurrency_limit: admission
+                    .as_ref()
+                    .and_then(AdmissionController::concurrency_limit)
+                    .map(|limit| round_to(limit, 2)),
+            }
+        });
+        let sum = measured_counts.iter().copied().map(f64::from).sum::<f64>();
+        let sum_sq = measured_counts
             .iter()
             .copied()
             .map(f64::from)
@@ -175,7 +633,7 @@ impl SimulationEngine {
         let jain_fairness = if sum == 0.0 || sum_sq == 0.0 {
             0.0
         } else {
-            (sum * sum) / (counts.len() as f64 * sum_sq)
+            (sum * sum) / (measured_counts.len() as f64 * sum_sq)
         };
 
         Ok(SimulationResult {
@@ -184,53 +642,166 @@ impl SimulationEngine {
             } else {
                 Vec::new()
             },
+            rejections,
             totals,
             metadata: RunMetadata {
                 algo: self.config.algo.to_string(),
                 tie_break: self.config.tie_break.label_with_seed(self.config.seed),
                 duration_ms: active_duration_ms,
+                balancers: self.config.balancers.as_ref().map(BalancerConfig::label),
             },
             phase1_metrics: Phase1Metrics {
-                response_time: ResponseTimePercentiles { p95_ms, p99_ms },
+                response_time,
+                response_time_distribution,
                 per_server_utilization,
                 jain_fairness: round_to(jain_fairness, 4),
                 throughput_rps: round_to(throughput_rps, 2),
                 avg_wait_ms,
+                avg_network_ms,
+                cross_zone_fraction,
+                sessions,
+                keys,
+                cache,
+                admission,
+                failures,
             },
+            timeseries,
         })
     }
+
+    fn log_server_event(&mut self, kind: EventKind, server_id: ServerId) -> Result<()> {
+        let server_idx = usize::from(server_id);
+        match &mut self.event_log {
+            Some(log) => log.write(&EventRecord {
+                server_id: Some(server_idx),
+                server: Some(&self.config.servers[server_idx].name),
+                ..EventRecord::new(self.state.time_ms, kind)
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn set_server_health(&mut self, server_id: ServerId, healthy: bool) {
+        let server_idx = usize::from(server_id);
+        self.state.servers[server_idx].healthy = healthy;
+        for view in &mut self.state.balancer_views {
+            view[server_idx].healthy = healthy;
+        }
+    }
 }
 
-fn schedule_requests(requests: Vec<Request>) -> BinaryHeap<Reverse<ScheduledEvent>> {
-    let mut event_queue = BinaryHeap::new();
-    for request in requests {
-        event_queue.push(Reverse(ScheduledEvent::new(
-            request.arrival_time_ms,
-            Event::RequestArrival(request),
-        )));
+fn schedule_outages(
+    event_queue: &mut BinaryHeap<Reverse<ScheduledEvent>>,
+    servers: &[ServerConfig],
+) {
+    for (idx, server) in servers.iter().enumerate() {
+        let server_id = ServerId::from(idx);
+        for outage in &server.outages {
+            event_queue.push(Reverse(ScheduledEvent::new(
+                outage.start_ms,
+                Event::ServerDown { server_id },
+            )));
+            event_queue.push(Reverse(ScheduledEvent::new(
+                outage.end_ms,
+                Event::ServerUp { server_id },
+            )));
+        }
+    }
+}
+
+/// Per-server latency breakdown of measured requests.
+/// A dispatched request awaiting completion, with the metrics it will record
+/// if its server stays up until its service ends.
+struct InFlightRequest {
+    server_index: usize,
+    balancer_id: usize,
+    service_completed_at: u64,
+    completed_at: u64,
+    measured: bool,
+    response_time: u64,
+    service_time: u64,
+    wait_time: u64,
+    queue_depth: u32,
+    network_ms: u64,
+}
+
+fn is_measured(arrival_time_ms: u64, window_start_ms: u64, window_end_ms: Option<u64>) -> bool {
+    arrival_time_ms >= window_start_ms
+        && window_end_ms.is_none_or(|end_ms| arrival_time_ms < end_ms)
+}
+
+struct ServerTally {
+    response_times: LatencyRecorder,
+    total_wait_ms: u64,
+    max_queue_depth: u32,
+    /// Service start times of requests still queued, in FIFO order.
+    queued_starts: VecDeque<u64>,
+}
+
+impl ServerTally {
+    fn new(metrics: &MetricsConfig) -> Self {
+        Self {
+            response_times: LatencyRecorder::new(metrics),
+            total_wait_ms: 0,
+            max_queue_depth: 0,
+            queued_starts: VecDeque::new(),
+        }
+    }
+
+    /// Tracks a request reaching the server at `reached_at` and starting
+    /// service at `started_at`; returns the queue depth it found, counting
+    /// itself if it has to wait.
+    fn enqueue(&mut self, reached_at: u64, started_at: u64) -> u32 {
+        while self
+            .queued_starts
+            .front()
+            .is_some_and(|&start| start <= reached_at)
+        {
+            self.queued_starts.pop_front();
+        }
+        if started_at > reached_at {
+            self.queued_starts.push_back(started_at);
+        }
+        self.queued_starts.len() as u32
+    }
+
+    /// Forgets the queue when the server goes down and its queued work fails.
+    fn clear_queue(&mut self) {
+        self.queued_starts.clear();
+    }
+
+    fn record(&mut self, response_ms: u64, wait_ms: u64, queue_depth: u32) {
+        self.response_times.record(response_ms);
+        self.total_wait_ms += wait_ms;
+        self.max_queue_depth = self.max_queue_depth.max(queue_depth);
     }
-    event_queue
 }
 
 fn build_server_summaries(
     servers: &[ServerState],
-    counts: &[u32],
+    measured_counts: &[u32],
     total_response_ms: &[u64],
+    total_service_ms: &[u64],
+    tallies: Vec<ServerTally>,
 ) -> Vec<ServerSummary> {
     servers
         .iter()
+        .zip(tallies)
         .enumerate()
-        .map(|(idx, server)| {
-            let count = counts[idx];
-            let avg_response_ms = if count == 0 {
-                0
-            } else {
-                total_response_ms[idx] / count as u64
-            };
+        .map(|(idx, (server, mut tally))| {
+            let measured = u64::from(measured_counts[idx]);
+            tally.response_times.finish();
             ServerSummary {
                 name: server.name.clone(),
-                requests: count,
-                avg_response_ms,
+                requests: measured_counts[idx],
+                avg_response_ms: total_response_ms[idx].checked_div(measured).unwrap_or(0),
+                p50_ms: tally.response_times.percentile(50.0),
+                p95_ms: tally.response_times.percentile(95.0),
+                p99_ms: tally.response_times.percentile(99.0),
+                avg_wait_ms: tally.total_wait_ms.checked_div(measured).unwrap_or(0),
+                avg_service_ms: total_service_ms[idx].checked_div(measured).unwrap_or(0),
+                max_queue_depth: tally.max_queue_depth,
+                busy_time_ms: total_service_ms[idx],
             }
         })
         .collect()
@@ -248,8 +819,34 @@ pub fn run_simulation_with_options(
     config: &SimConfig,
     store_assignments: bool,
 ) -> Result<SimulationResult> {
-    let strategy = build_strategy(config.algo.clone());
-    let mut engine = SimulationEngine::new(config.clone(), strategy);
+    run_simulation_with_registry(config, &StrategyRegistry::builtin(), store_assignments)
+}
+
+/// Runs `config` with `config.algo` looked up in `registry`, so library users
+/// can simulate strategies they registered themselves.
+pub fn run_simulation_with_registry(
+    config: &SimConfig,
+    registry: &StrategyRegistry,
+    store_assignments: bool,
+) -> Result<SimulationResult> {
+    let strategy = registry.resolve_algo(&config.algo)?;
+    let mut engine =
+        SimulationEngine::new(config.clone(), || build_routing_strategy(config, strategy));
+    engine.run(store_assignments)
+}
+
+/// Like `run_simulation_with_registry`, streaming every processed event to
+/// `event_log` as the run goes.
+pub fn run_simulation_with_event_log(
+    config: &SimConfig,
+    registry: &StrategyRegistry,
+    store_assignments: bool,
+    event_log: EventLog,
+) -> Result<SimulationResult> {
+    let strategy = registry.resolve_algo(&config.algo)?;
+    let mut engine =
+        SimulationEngine::new(config.clone(), || build_routing_strategy(config, strategy))
+            .with_event_log(event_log);
     engine.run(store_assignments)
 }
 
@@ -271,11 +868,104 @@ fn validate_config(config: &SimConfig) -> Result<()> {
         if names.contains(&server.name) {
             return Err(Error::DuplicateServerName(server.name.clone()));
         }
+        if server
+            .outages
+            .iter()
+            .any(|outage| outage.end_ms <= outage.start_ms)
+        {
+            return Err(Error::InvalidOutage(server.name.clone()));
+        }
+        let mut outages = server.outages.iter().collect::<Vec<_>>();
+        outages.sort_by_key(|outage| outage.start_ms);
+        if outages
+            .windows(2)
+            .any(|pair| pair[1].start_ms <= pair[0].end_ms)
+        {
+            return Err(Error::OverlappingOutages(server.name.clone()));
+        }
         names.insert(server.name.clone());
     }
 
     validate_request_profile(&config.requests)?;
 
+    if let Some(balancers) = &config.balancers {
+        if balancers.count == 0 {
+            return Err(Error::BalancersZero);
+        }
+        if let Some(interval) = balancers.sync_interval_ms {
+            if interval == 0 {
+                return Err(Error::InvalidSyncInterval(interval));
+            }
+        }
+        if balancers.sync_interval_ms.is_none() && balancers.sync_delay_ms > 0 {
+            return Err(Error::SyncDelayWithoutInterval);
+        }
+    }
+
+    if let Some(locality) = &config.locality {
+        if !(locality.spillover_threshold > 0.0 && locality.spillover_threshold <= 1.0) {
+            return Err(Error::InvalidSpilloverThreshold(
+                locality.spillover_threshold,
+            ));
+        }
+        if !config
+            .servers
+            .iter()
+            .any(|server| server.zone.as_ref() == Some(&locality.zone))
+        {
+            return Err(Error::UnknownZone(locality.zone.clone()));
+        }
+    }
+
+    if let Some(sessions) = &config.sessions {
+        if sessions.concurrent == 0 {
+            return Err(Error::SessionsZero);
+        }
+        if sessions.mean_length.is_nan() || sessions.mean_length < 1.0 {
+            return Err(Error::InvalidSessionLength(sessions.mean_length));
+        }
+    }
+
+    if let Some(keys) = &config.keys {
+        if keys.count == 0 {
+            return Err(Error::KeysZero);
+        }
+        if !(keys.zipf_exponent.is_finite() && keys.zipf_exponent >= 0.0) {
+            return Err(Error::InvalidZipfExponent(keys.zipf_exponent));
+        }
+    }
+
+    if let Some(cache) = &config.cache {
+        if cache.capacity == 0 {
+            return Err(Error::CacheCapacityZero);
+        }
+        if config.keys.is_none() {
+            return Err(Error::CacheWithoutKeys);
+        }
+    }
+
+    if let Some(admission) = &config.admission {
+        validate_admission(admission)?;
+    }
+
+    if let Some(metrics) = &config.metrics {
+        if !(1..=5).contains(&metrics.significant_digits) {
+            return Err(Error::InvalidSignificantDigits(metrics.significant_digits));
+        }
+    }
+
+    if let Some(measurement) = &config.measurement {
+        if measurement.window_ms == Some(0) {
+            return Err(Error::MeasurementWindowZero);
+        }
+    }
+
+    if let Some(failover) = &config.failover {
+        if !(failover.healthy_threshold > 0.0 && failover.healthy_threshold <= 1.0) {
+            return Err(Error::InvalidHealthyThreshold(failover.healthy_threshold));
+        }
+    }
+
     if matches!(config.tie_break, TieBreakConfig::Seeded) && config.seed.is_none() {
         return Err(Error::InvalidTieBreakSeed);
     }
@@ -283,6 +973,39 @@ fn validate_config(config: &SimConfig) -> Result<()> {
     Ok(())
 }
 
+fn validate_admission(admission: &AdmissionConfig) -> Result<()> {
+    if admission.max_in_flight == Some(0) {
+        return Err(Error::InvalidAdmission(
+            "max in-flight must be > 0".to_string(),
+        ));
+    }
+    if let Some(rate_limit) = &admission.rate_limit {
+        if !(rate_limit.rate.is_finite() && rate_limit.rate > 0.0) {
+            return Err(Error::InvalidAdmission(format!(
+                "rate limit must be > 0 (got {})",
+                rate_limit.rate
+            )));
+        }
+    }
+    if let Some(adaptive) = &admission.adaptive {
+        if adaptive.min_limit == 0
+            || adaptive.min_limit > adaptive.initial_limit
+            || adaptive.initial_limit > adaptive.max_limit
+        {
+            return Err(Error::InvalidAdmission(
+                "concurrency limits must satisfy 0 < min <= initial <= max".to_string(),
+            ));
+        }
+        if !(adaptive.backoff > 0.0 && adaptive.backoff < 1.0) {
+            return Err(Error::InvalidAdmission(format!(
+                "concurrency backoff must be in (0, 1) (got {})",
+                adaptive.backoff
+            )));
+        }
+    }
+    Ok(())
+}
+
 fn validate_request_profile(profile: &RequestProfile) -> Result<()> {
     match *profile {
         RequestProfile::FixedCount(0) => Err(Error::RequestsZero),
@@ -305,51 +1028,12 @@ fn validate_request_profile(profile: &RequestProfile) -> Result<()> {
     }
 }
 
-fn build_requests(profile: &RequestProfile, seed: Option<u64>) -> Result<Vec<Request>> {
-    validate_request_profile(profile)?;
-    match profile {
-        RequestProfile::FixedCount(count) => Ok((0..*count)
-            .map(|idx| Request {
-                id: idx + 1,
-                arrival_time_ms: idx as u64,
-            })
-            .collect()),
-        RequestProfile::Poisson { rate, duration_ms } => {
-            let mut rng = StdRng::seed_from_u64(seed.unwrap_or(0));
-            let lambda_ms = rate / 1000.0;
-            let mut requests = Vec::new();
-            let mut time = 0.0;
-            let mut id = 1usize;
-            while time < *duration_ms as f64 {
-                let mut u = rng.gen::<f64>();
-                if u <= f64::MIN_POSITIVE {
-                    u = f64::MIN_POSITIVE;
-                }
-                let inter_arrival = -u.ln() / lambda_ms;
-                time += inter_arrival;
-                if time >= *duration_ms as f64 {
-                    break;
-                }
-                requests.push(Request {
-                    id,
-                    arrival_time_ms: time.floor() as u64,
-                });
-                id += 1;
-            }
-
-            if requests.is_empty() {
-                return Err(Error::RequestsZero);
-            }
-
-            Ok(requests)
-        }
-        RequestProfile::Burst { count, at_ms } => Ok((0..*count)
-            .map(|idx| Request {
-                id: idx + 1,
-                arrival_time_ms: *at_ms,
-            })
-            .collect()),
-    }
+fn balancer_count(config: &SimConfig) -> usize {
+    config
+        .balancers
+        .as_ref()
+        .map(|balancers| balancers.count)
+        .unwrap_or(1)
 }
 
 fn init_server_state(servers: &[ServerConfig]) -> Vec<ServerState> {
@@ -365,10 +1049,35 @@ fn init_server_state(servers: &[ServerConfig]) -> Vec<ServerState> {
             pick_count: 0,
             in_flight: 0,
             next_available_ms: 0,
+            network_latency_ms: server.network_latency_ms,
+            healthy: true,
         })
         .collect()
 }
 
+/// Arrival times `[start, end)` whose requests count toward the metrics.
+fn measurement_window(config: &SimConfig) -> (u64, Option<u64>) {
+    let start_ms = config.requests.start_ms();
+    match &config.measurement {
+        Some(measurement) => {
+            let window_start_ms = start_ms + measurement.warmup_ms;
+            let window_end_ms = measurement
+                .window_ms
+                .map(|window_ms| window_start_ms + window_ms);
+            (window_start_ms, window_end_ms)
+        }
+        None => (start_ms, None),
+    }
+}
+
+fn network_hop_ms(rng: &mut StdRng, server: &ServerState, jitter_ms: u64) -> u64 {
+    if jitter_ms == 0 {
+        server.network_latency_ms
+    } else {
+        server.network_latency_ms + rng.gen_range(0..=jitter_ms)
+    }
+}
+
 struct StableRng;
 
 impl RngCore for StableRng {
@@ -390,13 +1099,12 @@ impl RngCore for StableRng {
     }
 }
 
-fn nearest_rank_percentile(sorted: &[u64], percentile: f64) -> Option<u64> {
-    if sorted.is_empty() {
-        return None;
+fn ratio(numerator: u64, denominator: u64) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
     }
-    let rank = ((percentile / 100.0) * sorted.len() as f64).ceil() as usize;
-    let idx = rank.saturating_sub(1).min(sorted.len() - 1);
-    Some(sorted[idx])
 }
 
 fn round_to(value: f64, decimals: u32) -> f64 {
@@ -410,38 +1118,28 @@ fn round_to(value: f64, decimals: u32) -> f64 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::AlgoConfig;
+    use crate::models::{
+        AdaptiveLimitConfig, AlgoConfig, CacheConfig, DistributionKind, KeyConfig, LatencyBackend,
+        LocalityConfig, MeasurementConfig, MetricsConfig, OutageWindow, Percentile, SessionConfig,
+        SessionLength, SpilloverMode, TimeSeriesSampling,
+    };
+    use crate::state::ResponseTimeDistribution;
 
     fn config_with_servers(servers: Vec<ServerConfig>) -> SimConfig {
-        SimConfig {
+        SimConfig::new(
             servers,
-            requests: RequestProfile::FixedCount(1),
-            algo: AlgoConfig::RoundRobin,
-            tie_break: TieBreakConfig::Stable,
-            seed: None,
-        }
+            RequestProfile::FixedCount(1),
+            AlgoConfig::new("round-robin"),
+        )
     }
 
     #[test]
     fn least_connections_accounts_for_completed_requests() {
-        let config = SimConfig {
-            servers: vec![
-                ServerConfig {
-                    name: "fast".to_string(),
-                    base_latency_ms: 1,
-                    weight: 1,
-                },
-                ServerConfig {
-                    name: "slow".to_string(),
-                    base_latency_ms: 100,
-                    weight: 1,
-                },
-            ],
-            requests: RequestProfile::FixedCount(2),
-            algo: AlgoConfig::LeastConnections,
-            tie_break: TieBreakConfig::Stable,
-            seed: None,
-        };
+        let config = SimConfig::new(
+            vec![ServerConfig::new("fast", 1), ServerConfig::new("slow", 100)],
+            RequestProfile::FixedCount(2),
+            AlgoConfig::new("least-connections"),
+        );
         let result = run_simulation(&config).expect("simulation should succeed");
         let assigned = result
             .assignments
@@ -454,27 +1152,17 @@ mod tests {
     #[test]
     fn seeded_tiebreak_is_deterministic_in_engine() {
         let config = SimConfig {
-            servers: vec![
-                ServerConfig {
-                    name: "a".to_string(),
-                    base_latency_ms: 1,
-                    weight: 1,
-                },
-                ServerConfig {
-                    name: "b".to_string(),
-                    base_latency_ms: 1,
-                    weight: 1,
-                },
-                ServerConfig {
-                    name: "c".to_string(),
-                    base_latency_ms: 1,
-                    weight: 1,
-                },
-            ],
-            requests: RequestProfile::FixedCount(3),
-            algo: AlgoConfig::LeastConnections,
             tie_break: TieBreakConfig::Seeded,
             seed: Some(42),
+            ..SimConfig::new(
+                vec![
+                    ServerConfig::new("a", 1),
+                    ServerConfig::new("b", 1),
+                    ServerConfig::new("c", 1),
+                ],
+                RequestProfile::FixedCount(3),
+                AlgoConfig::new("least-connections"),
+            )
         };
         let result_a = run_simulation(&config).expect("simulation should succeed");
         let result_b = run_simulation(&config).expect("simulation should succeed");
@@ -495,17 +1183,11 @@ mod tests {
 
     #[test]
     fn assignments_include_response_time_metrics() {
-        let config = SimConfig {
-            servers: vec![ServerConfig {
-                name: "api".to_string(),
-                base_latency_ms: 5,
-                weight: 1,
-            }],
-            requests: RequestProfile::FixedCount(2),
-            algo: AlgoConfig::RoundRobin,
-            tie_break: TieBreakConfig::Stable,
-            seed: None,
-        };
+        let config = SimConfig::new(
+            vec![ServerConfig::new("api", 5)],
+            RequestProfile::FixedCount(2),
+            AlgoConfig::new("round-robin"),
+        );
         let result = run_simulation(&config).expect("simulation should succeed");
 
         let started: Vec<u64> = result
@@ -534,29 +1216,15 @@ mod tests {
 
     #[test]
     fn summary_preserves_input_order() {
-        let config = SimConfig {
-            servers: vec![
-                ServerConfig {
-                    name: "api".to_string(),
-                    base_latency_ms: 10,
-                    weight: 1,
-                },
-                ServerConfig {
-                    name: "db".to_string(),
-                    base_latency_ms: 20,
-                    weight: 1,
-                },
-                ServerConfig {
-                    name: "cache".to_string(),
-                    base_latency_ms: 30,
-                    weight: 1,
-                },
+        let config = SimConfig::new(
+            vec![
+                ServerConfig::new("api", 10),
+                ServerConfig::new("db", 20),
+                ServerConfig::new("cache", 30),
             ],
-            requests: RequestProfile::FixedCount(2),
-            algo: AlgoConfig::RoundRobin,
-            tie_break: TieBreakConfig::Stable,
-            seed: None,
-        };
+            RequestProfile::FixedCount(2),
+            AlgoConfig::new("round-robin"),
+        );
         let result = run_simulation(&config).expect("simulation should succeed");
         let names: Vec<&str> = result
             .totals
@@ -568,59 +1236,34 @@ mod tests {
 
     #[test]
     fn duplicate_server_names_error() {
-        let config = config_with_servers(vec![
-            ServerConfig {
-                name: "a".to_string(),
-                base_latency_ms: 10,
-                weight: 1,
-            },
-            ServerConfig {
-                name: "a".to_string(),
-                base_latency_ms: 20,
-                weight: 1,
-            },
-        ]);
+        let config =
+            config_with_servers(vec![ServerConfig::new("a", 10), ServerConfig::new("a", 20)]);
         let result = run_simulation(&config);
         assert!(result.is_err());
     }
 
     #[test]
     fn empty_servers_error() {
-        let config = SimConfig {
-            servers: Vec::new(),
-            requests: RequestProfile::FixedCount(1),
-            algo: AlgoConfig::RoundRobin,
-            tie_break: TieBreakConfig::Stable,
-            seed: None,
-        };
+        let config = SimConfig::new(
+            Vec::new(),
+            RequestProfile::FixedCount(1),
+            AlgoConfig::new("round-robin"),
+        );
         let result = run_simulation(&config);
         assert!(result.is_err());
     }
 
     #[test]
     fn phase1_metrics_are_deterministic() {
-        let config = SimConfig {
-            servers: vec![
-                ServerConfig {
-                    name: "a".to_string(),
-                    base_latency_ms: 1,
-                    weight: 1,
-                },
-                ServerConfig {
-                    name: "b".to_string(),
-                    base_latency_ms: 1,
-                    weight: 1,
-                },
-            ],
-            requests: RequestProfile::FixedCount(2),
-            algo: AlgoConfig::RoundRobin,
-            tie_break: TieBreakConfig::Stable,
-            seed: None,
-        };
+        let config = SimConfig::new(
+            vec![ServerConfig::new("a", 1), ServerConfig::new("b", 1)],
+            RequestProfile::FixedCount(2),
+            AlgoConfig::new("round-robin"),
+        );
         let result = run_simulation(&config).expect("simulation should succeed");
 
-        assert_eq!(result.phase1_metrics.response_time.p95_ms, Some(1));
-        assert_eq!(result.phase1_metrics.response_time.p99_ms, Some(1));
+        assert_eq!(result.phase1_metrics.response_time.p95_ms(), Some(1));
+        assert_eq!(result.phase1_metrics.response_time.p99_ms(), Some(1));
         assert_eq!(
             result
                 .phase1_metrics
@@ -634,4 +1277,814 @@ mod tests {
         assert_eq!(result.phase1_metrics.throughput_rps, 1000.0);
         assert_eq!(result.phase1_metrics.avg_wait_ms, 0);
     }
+
+    #[test]
+    fn measurement_window_excludes_warmup_and_late_arrivals() {
+        let mut config = config_with_servers(vec![ServerConfig::new("a", 10)]);
+        // Arrivals at 0..10 queue on one server: request i completes at 10(i+1).
+        config.requests = RequestProfile::FixedCount(10);
+        config.measurement = Some(MeasurementConfig {
+            warmup_ms: 5,
+            window_ms: None,
+        });
+
+        let warm = run_simulation(&config).expect("simulation should succeed");
+        assert_eq!(warm.totals[0].requests, 5);
+        assert_eq!(warm.metadata.duration_ms, 95);
+        assert_eq!(warm.phase1_metrics.response_time.p99_ms(), Some(91));
+        assert_eq!(warm.phase1_metrics.avg_wait_ms, 63);
+        assert_eq!(warm.phase1_metrics.throughput_rps, 52.63);
+        assert_eq!(
+            warm.phase1_metrics.per_server_utilization[0].utilization_pct,
+            52.63
+        );
+
+        config.measurement = Some(MeasurementConfig {
+            warmup_ms: 5,
+            window_ms: Some(2),
+        });
+        let windowed = run_simulation(&config).expect("simulation should succeed");
+        assert_eq!(windowed.metadata.duration_ms, 65);
+        assert_eq!(windowed.phase1_metrics.response_time.p99_ms(), Some(64));
+        assert_eq!(windowed.phase1_metrics.throughput_rps, 30.77);
+
+        config.measurement = Some(MeasurementConfig {
+            warmup_ms: 0,
+            window_ms: Some(0),
+        });
+        assert!(matches!(
+            run_simulation(&config),
+            Err(Error::MeasurementWindowZero)
+        ));
+    }
+
+    #[test]
+    fn server_summaries_break_down_latency_per_server() {
+        let mut config =
+            config_with_servers(vec![ServerConfig::new("a", 10), ServerConfig::new("b", 40)]);
+        // Round-robin sends arrivals 0, 2, 4 to a and 1, 3, 5 to b.
+        config.requests = RequestProfile::FixedCount(6);
+
+        let result = run_simulation(&config).expect("simulation should succeed");
+        let a = &result.totals[0];
+        assert_eq!(
+            (a.p50_ms, a.p95_ms, a.p99_ms),
+            (Some(18), Some(26), Some(26))
+        );
+        assert_eq!((a.avg_wait_ms, a.avg_service_ms), (8, 10));
+        assert_eq!((a.max_queue_depth, a.busy_time_ms), (2, 30));
+        let b = &result.totals[1];
+        assert_eq!((b.p50_ms, b.p99_ms), (Some(78), Some(116)));
+        assert_eq!((b.avg_wait_ms, b.avg_service_ms), (38, 40));
+        assert_eq!((b.max_queue_depth, b.busy_time_ms), (2, 120));
+
+        // Warm-up arrivals still queue but are left out of every field.
+        config.measurement = Some(MeasurementConfig {
+            warmup_ms: 4,
+            window_ms: None,
+        });
+        let warm = run_simulation(&config).expect("simulation should succeed");
+        let a = &warm.totals[0];
+        assert_eq!((a.requests, a.p50_ms, a.avg_wait_ms), (1, Some(26), 16));
+        assert_eq!((a.avg_response_ms, a.max_queue_depth), (26, 2));
+    }
+
+    #[test]
+    fn timeseries_samples_load_on_every_change() {
+        let mut config =
+            config_with_servers(vec![ServerConfig::new("a", 10), ServerConfig::new("b", 40)]);
+        config.requests = RequestProfile::FixedCount(2);
+        config.metrics = Some(MetricsConfig {
+            timeseries: Some(TimeSeriesSampling::Events),
+            ..MetricsConfig::default()
+        });
+
+        let result = run_simulation(&config).expect("simulation should succeed");
+        let timeseries = result.timeseries.expect("time series should be recorded");
+        assert_eq!(timeseries.servers, ["a", "b"]);
+        let samples: Vec<_> = timeseries
+            .samples
+            .iter()
+            .map(|sample| {
+                (
+                    sample.time_ms,
+                    sample.in_flight.clone(),
+                    sample.busy.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            samples,
+            [
+                (0, vec![1, 0], vec![true, false]),
+                (1, vec![1, 1], vec![true, true]),
+                (10, vec![0, 1], vec![false, true]),
+                (41, vec![0, 0], vec![false, false]),
+            ]
+        );
+
+        config.metrics = Some(MetricsConfig {
+            timeseries: Some(TimeSeriesSampling::Interval(25)),
+            ..MetricsConfig::default()
+        });
+        let result = run_simulation(&config).expect("simulation should succeed");
+        let times: Vec<u64> = result
+            .timeseries
+            .expect("time series should be recorded")
+            .samples
+            .iter()
+            .map(|sample| sample.time_ms)
+            .collect();
+        assert_eq!(times, [0, 25]);
+    }
+
+    #[test]
+    fn configured_percentiles_and_distribution_are_reported() {
+        let mut config = config_with_servers(vec![ServerConfig::new("a", 10)]);
+        // Response times 10, 19, 28, 37.
+        config.requests = RequestProfile::FixedCount(4);
+        let percentiles = ["p50", "p75", "max"].map(|text| Percentile::parse(text).unwrap());
+        config.metrics = Some(MetricsConfig {
+            percentiles: percentiles.to_vec(),
+            distribution: Some(DistributionKind::Cdf),
+            ..MetricsConfig::default()
+        });
+
+        let result = run_simulation(&config).expect("simulation should succeed");
+        let response_time = &result.phase1_metrics.response_time;
+        assert_eq!(
+            response_time.iter().collect::<Vec<_>>(),
+            vec![
+                (percentiles[0], Some(19)),
+                (percentiles[1], Some(28)),
+                (percentiles[2], Some(37)),
+            ]
+        );
+        assert_eq!(response_time.p99_ms(), None);
+        let Some(ResponseTimeDistribution::Cdf(points)) =
+            &result.phase1_metrics.response_time_distribution
+        else {
+            panic!("expected a CDF");
+        };
+        assert_eq!(
+            points
+                .iter()
+                .map(|point| (point.response_ms, point.fraction))
+                .collect::<Vec<_>>(),
+            vec![(10, 0.25), (19, 0.5), (28, 0.75), (37, 1.0)]
+        );
+
+        // Small values fit the HDR histogram's exact buckets.
+        let mut metrics = config.metrics.take().unwrap();
+        metrics.backend = LatencyBackend::Hdr;
+        metrics.significant_digits = 1;
+        config.metrics = Some(metrics);
+        let hdr = run_simulation(&config).expect("simulation should succeed");
+        assert_eq!(
+            hdr.phase1_metrics.response_time.iter().collect::<Vec<_>>(),
+            response_time.iter().collect::<Vec<_>>()
+        );
+
+        config.metrics.as_mut().unwrap().significant_digits = 6;
+        assert!(matches!(
+            run_simulation(&config),
+            Err(Error::InvalidSignificantDigits(6))
+        ));
+    }
+
+    #[test]
+    fn event_queue_stays_flat_as_request_count_grows() {
+        let peak_queued_events = |requests: usize| {
+            let config = SimConfig {
+                sessions: Some(SessionConfig {
+                    concurrent: 4,
+                    mean_length: 8.0,
+                    length: SessionLength::Geometric,
+                    max_in_flight: None,
+                }),
+                keys: Some(KeyConfig {
+                    count: 64,
+                    zipf_exponent: 1.0,
+                }),
+                metrics: Some(MetricsConfig {
+                    backend: LatencyBackend::Hdr,
+                    ..MetricsConfig::default()
+                }),
+                ..SimConfig::new(
+                    vec![ServerConfig::new("a", 1), ServerConfig::new("b", 1)],
+                    RequestProfile::FixedCount(requests),
+                    AlgoConfig::new("least-connections"),
+                )
+            };
+            let registry = StrategyRegistry::builtin();
+            let strategy = registry.resolve_algo(&config.algo).expect("built-in");
+            let mut engine =
+                SimulationEngine::new(config.clone(), || build_routing_strategy(&config, strategy));
+            let result = engine.run(false).expect("simulation should succeed");
+            assert_eq!(
+                result
+                    .totals
+                    .iter()
+                    .map(|total| total.requests)
+                    .sum::<u32>() as usize,
+                requests
+            );
+            engine.peak_queued_events
+        };
+
+        let small = peak_queued_events(1_000);
+        assert!(small <= 4, "peak of {} queued events", small);
+        assert_eq!(peak_queued_events(100_000), small);
+    }
+
+    fn two_balancer_config(sync_interval_ms: Option<u64>) -> SimConfig {
+        SimConfig {
+            balancers: Some(BalancerConfig {
+                count: 2,
+                sync_interval_ms,
+                sync_delay_ms: 0,
+            }),
+            ..SimConfig::new(
+                vec![ServerConfig::new("a", 100), ServerConfig::new("b", 100)],
+                RequestProfile::FixedCount(2),
+                AlgoConfig::new("least-connections"),
+            )
+        }
+    }
+
+    #[test]
+    fn independent_balancers_herd_on_stale_views() {
+        let result = run_simulation(&two_balancer_config(None)).expect("simulation should succeed");
+        let assigned = result
+            .assignments
+            .iter()
+            .map(|assignment| assignment.server_id)
+            .collect::<Vec<_>>();
+        assert_eq!(assigned, vec![ServerId::from(0), ServerId::from(0)]);
+        assert_eq!(result.metadata.balancers.as_deref(), Some("2 (no sync)"));
+    }
+
+    #[test]
+    fn view_sync_shares_load_between_balancers() {
+        let result =
+            run_simulation(&two_balancer_config(Some(1))).expect("simulation should succeed");
+        let assigned = result
+            .assignments
+            .iter()
+            .map(|assignment| assignment.server_id)
+            .collect::<Vec<_>>();
+        assert_eq!(assigned, vec![ServerId::from(0), ServerId::from(1)]);
+    }
+
+    #[test]
+    fn zero_balancers_error() {
+        let mut config = two_balancer_config(None);
+        config.balancers = Some(BalancerConfig {
+            count: 0,
+            sync_interval_ms: None,
+            sync_delay_ms: 0,
+        });
+        assert!(matches!(run_simulation(&config), Err(Error::BalancersZero)));
+
+        config.balancers = Some(BalancerConfig {
+            count: 2,
+            sync_interval_ms: None,
+            sync_delay_ms: 5,
+        });
+        assert!(matches!(
+            run_simulation(&config),
+            Err(Error::SyncDelayWithoutInterval)
+        ));
+    }
+
+    #[test]
+    fn network_latency_is_reported_separately_from_service_time() {
+        let config = SimConfig::new(
+            vec![ServerConfig {
+                network_latency_ms: 5,
+                ..ServerConfig::new("remote", 10)
+            }],
+            RequestProfile::FixedCount(1),
+            AlgoConfig::new("round-robin"),
+        );
+        let result = run_simulation(&config).expect("simulation should succeed");
+
+        let assignment = &result.assignments[0];
+        assert_eq!(assignment.started_at, 5);
+        assert_eq!(assignment.completed_at, 20);
+        assert_eq!(assignment.network_ms, 10);
+        assert_eq!(result.phase1_metrics.response_time.p99_ms(), Some(20));
+        assert_eq!(result.phase1_metrics.avg_network_ms, 10);
+        assert_eq!(result.phase1_metrics.avg_wait_ms, 0);
+    }
+
+    #[test]
+    fn least_response_time_weighs_network_latency() {
+        let config = SimConfig::new(
+            vec![
+                ServerConfig {
+                    network_latency_ms: 20,
+                    ..ServerConfig::new("cross-region", 10)
+                },
+                ServerConfig::new("local", 20),
+            ],
+            RequestProfile::FixedCount(1),
+            AlgoConfig::new("least-response-time"),
+        );
+        let result = run_simulation(&config).expect("simulation should succeed");
+
+        assert_eq!(result.assignments[0].server_id, ServerId::from(1));
+        assert_eq!(result.assignments[0].score, Some(20));
+    }
+
+    #[test]
+    fn zone_aware_routing_reports_cross_zone_fraction() {
+        let config = SimConfig {
+            locality: Some(LocalityConfig {
+                zone: "us-east".to_string(),
+                spillover_threshold: 1.0,
+                spillover: SpilloverMode::Threshold,
+            }),
+            ..SimConfig::new(
+                vec![
+                    ServerConfig {
+                        zone: Some("us-east".to_string()),
+                        ..ServerConfig::new("east", 10)
+                    },
+                    ServerConfig {
+                        zone: Some("us-west".to_string()),
+                        ..ServerConfig::new("west", 10)
+                    },
+                ],
+                RequestProfile::FixedCount(2),
+                AlgoConfig::new("round-robin"),
+            )
+        };
+        let result = run_simulation(&config).expect("simulation should succeed");
+
+        let assigned = result
+            .assignments
+            .iter()
+            .map(|assignment| assignment.server_id)
+            .collect::<Vec<_>>();
+        assert_eq!(assigned, vec![ServerId::from(0), ServerId::from(1)]);
+        assert_eq!(result.phase1_metrics.cross_zone_fraction, Some(0.5));
+    }
+
+    #[test]
+    fn outage_fails_over_to_backup_tier() {
+        let config = SimConfig::new(
+            vec![
+                ServerConfig {
+                    outages: vec![OutageWindow {
+                        start_ms: 0,
+                        end_ms: 2,
+                    }],
+                    ..ServerConfig::new("primary", 1)
+                },
+                ServerConfig {
+                    priority: 1,
+                    ..ServerConfig::new("backup", 1)
+                },
+            ],
+            RequestProfile::FixedCount(4),
+            AlgoConfig::new("round-robin"),
+        );
+        let result = run_simulation(&config).expect("simulation should succeed");
+
+        let assigned = result
+            .assignments
+            .iter()
+            .map(|assignment| assignment.server_id)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            assigned,
+            vec![
+                ServerId::from(1),
+                ServerId::from(1),
+                ServerId::from(0),
+                ServerId::from(0)
+            ]
+        );
+    }
+
+    #[test]
+    fn outage_fails_queued_work_and_drops_requests_with_no_server() {
+        let down = vec![OutageWindow {
+            start_ms: 5,
+            end_ms: 100,
+        }];
+        let config = SimConfig::new(
+            vec![
+                ServerConfig {
+                    outages: down.clone(),
+                    ..ServerConfig::new("a", 10)
+                },
+                ServerConfig {
+                    outages: down,
+                    ..ServerConfig::new("b", 20)
+                },
+            ],
+            RequestProfile::FixedCount(10),
+            AlgoConfig::new("round-robin"),
+        );
+        let result = run_simulation(&config).expect("simulation should succeed");
+
+        // Requests 1-5 are still queued or in service at 5 ms; 6-10 arrive
+        // with both servers down. None of them is served.
+        let failures = result
+            .phase1_metrics
+            .failures
+            .expect("outages report failures");
+        assert_eq!(failures.failed_in_flight, 5);
+        assert_eq!(failures.dropped_no_server, 5);
+        assert_eq!(failures.failed_fraction, 1.0);
+        assert_eq!(result.phase1_metrics.response_time.get(50.0), None);
+        assert!(result
+            .phase1_metrics
+            .per_server_utilization
+            .iter()
+            .all(|server| server.utilization_pct == 0.0));
+    }
+
+    #[test]
+    fn overlapping_outages_are_rejected() {
+        let config = SimConfig::new(
+            vec![ServerConfig {
+                outages: vec![
+                    OutageWindow {
+                        start_ms: 10,
+                        end_ms: 80,
+                    },
+                    OutageWindow {
+                        start_ms: 0,
+                        end_ms: 50,
+                    },
+                ],
+                ..ServerConfig::new("a", 1)
+            }],
+            RequestProfile::FixedCount(4),
+            AlgoConfig::new("round-robin"),
+        );
+
+        let err = run_simulation(&config).expect_err("overlapping outages should fail");
+        assert!(matches!(err, Error::OverlappingOutages(name) if name == "a"));
+    }
+
+    fn session_config(max_in_flight: Option<u32>) -> SimConfig {
+        SimConfig {
+            sessions: Some(SessionConfig {
+                concurrent: 1,
+                mean_length: 100.0,
+                length: SessionLength::Fixed,
+                max_in_flight,
+            }),
+            ..SimConfig::new(
+                vec![ServerConfig::new("a", 10), ServerConfig::new("b", 10)],
+                RequestProfile::FixedCount(4),
+                AlgoConfig::new("least-connections"),
+            )
+        }
+    }
+
+    #[test]
+    fn sticky_sessions_pin_requests_and_report_imbalance() {
+        let result = run_simulation(&session_config(None)).expect("simulation should succeed");
+
+        assert!(result
+            .assignments
+            .iter()
+            .all(|assignment| assignment.server_id == ServerId::from(0)));
+        let sessions = result
+            .phase1_metrics
+            .sessions
+            .expect("session metrics should be reported");
+        assert_eq!(sessions.sessions, 1);
+        assert_eq!(sessions.stickiness_breaks, 0);
+        assert_eq!(sessions.load_imbalance, 2.0);
+    }
+
+    #[test]
+    fn sticky_sessions_break_on_overloaded_server() {
+        let result = run_simulation(&session_config(Some(2))).expect("simulation should succeed");
+
+        let sessions = result
+            .phase1_metrics
+            .sessions
+            .expect("session metrics should be reported");
+        assert_eq!(sessions.stickiness_breaks, 1);
+        assert_eq!(result.totals[0].requests, 2);
+        assert_eq!(result.totals[1].requests, 2);
+    }
+
+    #[test]
+    fn sessions_stay_sticky_across_balancers() {
+        let stickiness_breaks = |count: usize| {
+            let config = SimConfig {
+                balancers: Some(BalancerConfig {
+                    count,
+                    sync_interval_ms: None,
+                    sync_delay_ms: 0,
+                }),
+                sessions: Some(SessionConfig {
+                    concurrent: 5,
+                    mean_length: 10.0,
+                    length: SessionLength::Fixed,
+                    max_in_flight: None,
+                }),
+                seed: Some(1),
+                ..SimConfig::new(
+                    vec![ServerConfig::new("a", 10), ServerConfig::new("b", 20)],
+                    RequestProfile::FixedCount(200),
+                    AlgoConfig::new("least-connections"),
+                )
+            };
+            let result = run_simulation(&config).expect("simulation should succeed");
+            let sessions = result
+                .phase1_metrics
+                .sessions
+                .expect("session metrics should be reported");
+            sessions.stickiness_breaks
+        };
+
+        assert_eq!(stickiness_breaks(1), 0);
+        assert_eq!(stickiness_breaks(3), 0);
+    }
+
+    fn keyed_config(algo: AlgoConfig) -> SimConfig {
+        let servers = (0..4)
+            .map(|idx| ServerConfig::new(format!("srv-{}", idx), 10))
+            .collect();
+        SimConfig {
+            tie_break: TieBreakConfig::Seeded,
+            seed: Some(11),
+            keys: Some(KeyConfig {
+                count: 16,
+                zipf_exponent: 1.0,
+            }),
+            ..SimConfig::new(
+                servers,
+                RequestProfile::Poisson {
+                    rate: 300.0,
+                    duration_ms: 2_000,
+                },
+                algo,
+            )
+        }
+    }
+
+    fn hash_with_epsilon(epsilon: f64) -> AlgoConfig {
+        AlgoConfig::new("consistent-hash").with_param("epsilon", epsilon)
+    }
+
+    #[test]
+    fn consistent_hash_keeps_keys_on_their_servers() {
+        let hashed = run_simulation(&keyed_config(hash_with_epsilon(100.0)))
+            .expect("simulation should succeed");
+        let spread = run_simulation(&keyed_config(AlgoConfig::new("round-robin")))
+            .expect("simulation should succeed");
+
+        let hashed_keys = hashed.phase1_metrics.keys.expect("key metrics");
+        let spread_keys = spread.phase1_metrics.keys.expect("key metrics");
+        assert_eq!(hashed_keys.key_locality, 1.0);
+        assert!(spread_keys.key_locality < 0.5);
+        assert_eq!(hashed_keys.distinct_keys, spread_keys.distinct_keys);
+    }
+
+    #[test]
+    fn tighter_hash_load_bound_trades_locality_for_balance() {
+        let loose = run_simulation(&keyed_config(hash_with_epsilon(1.0)))
+            .expect("simulation should succeed");
+        let tight = run_simulation(&keyed_config(hash_with_epsilon(0.0)))
+            .expect("simulation should succeed");
+
+        let loose_keys = loose.phase1_metrics.keys.expect("key metrics");
+        let tight_keys = tight.phase1_metrics.keys.expect("key metrics");
+        assert!(tight_keys.key_locality < loose_keys.key_locality);
+        assert!(tight.phase1_metrics.jain_fairness > loose.phase1_metrics.jain_fairness);
+    }
+
+    #[test]
+    fn key_affine_routing_raises_cache_hit_ratio() {
+        let cached = |algo| {
+            let mut config = keyed_config(algo);
+            config.cache = Some(CacheConfig {
+                capacity: 4,
+                hit_latency_ms: 2,
+            });
+            run_simulation(&config).expect("simulation should succeed")
+        };
+        let hashed = cached(AlgoConfig::new("consistent-hash"));
+        let spread = cached(AlgoConfig::new("round-robin"));
+
+        let hashed_cache = hashed.phase1_metrics.cache.as_ref().expect("cache metrics");
+        let spread_cache = spread.phase1_metrics.cache.as_ref().expect("cache metrics");
+        assert!(hashed_cache.hit_ratio > spread_cache.hit_ratio);
+        assert_eq!(hashed_cache.per_server.len(), 4);
+        let lookups: u64 = hashed_cache.per_server.iter().map(|s| s.lookups).sum();
+        assert_eq!(lookups as usize, hashed.assignments.len());
+        let total_service_ms = |result: &SimulationResult| {
+            result
+                .assignments
+                .iter()
+                .map(|a| a.completed_at - a.started_at)
+                .sum::<u64>()
+        };
+        assert!(total_service_ms(&hashed) < total_service_ms(&spread));
+    }
+
+    #[test]
+    fn cache_requires_request_keys() {
+        let mut config = keyed_config(AlgoConfig::new("round-robin"));
+        config.keys = None;
+        config.cache = Some(CacheConfig {
+            capacity: 4,
+            hit_latency_ms: 2,
+        });
+
+        assert!(matches!(
+            run_simulation(&config),
+            Err(Error::CacheWithoutKeys)
+        ));
+    }
+
+    fn overload_config(admission: Option<AdmissionConfig>) -> SimConfig {
+        let mut config = config_with_servers(vec![
+            ServerConfig::new("fast", 10),
+            ServerConfig::new("slow", 20),
+        ]);
+        config.requests = RequestProfile::Poisson {
+            rate: 225.0,
+            duration_ms: 2_000,
+        };
+        config.seed = Some(3);
+        config.admission = admission;
+        config
+    }
+
+    #[test]
+    fn admission_control_sheds_load_at_the_balancer() {
+        let queued = run_simulation(&overload_config(None)).expect("simulation should succeed");
+        let shed = run_simulation(&overload_config(Some(AdmissionConfig {
+            max_in_flight: Some(4),
+            rate_limit: None,
+            adaptive: None,
+        })))
+        .expect("simulation should succeed");
+
+        let admission = shed.phase1_metrics.admission.expect("admission metrics");
+        assert!(admission.rejected > 0);
+        assert_eq!(admission.rejected, admission.rejected_max_in_flight);
+        assert_eq!(admission.rejected as usize, shed.rejections.len());
+        assert_eq!(
+            admission.admitted as usize + shed.rejections.len(),
+            queued.assignments.len()
+        );
+        assert!(queued.rejections.is_empty());
+        assert!(
+            shed.phase1_metrics.response_time.p99_ms()
+                < queued.phase1_metrics.response_time.p99_ms()
+        );
+    }
+
+    #[test]
+    fn adaptive_limit_reports_final_concurrency_limit() {
+        let result = run_simulation(&overload_config(Some(AdmissionConfig {
+            max_in_flight: None,
+            rate_limit: None,
+            adaptive: Some(AdaptiveLimitConfig::with_threshold(40)),
+        })))
+        .expect("simulation should succeed");
+
+        let admission = result.phase1_metrics.admission.expect("admission metrics");
+        assert_eq!(admission.rejected, admission.rejected_concurrency_limit);
+        assert!(admission.rejected > 0);
+        let limit = admission
+            .final_concurrency_limit
+            .expect("adaptive limit should be reported");
+        assert!(limit < f64::from(AdaptiveLimitConfig::with_threshold(40).initial_limit));
+    }
+
+    #[test]
+    fn admission_limits_are_shared_across_balancers() {
+        let mut config =
+            config_with_servers(vec![ServerConfig::new("a", 10), ServerConfig::new("b", 10)]);
+        config.requests = RequestProfile::Burst {
+            count: 10,
+            at_ms: 0,
+        };
+        config.balancers = Some(BalancerConfig {
+            count: 3,
+            sync_interval_ms: None,
+            sync_delay_ms: 0,
+        });
+        config.admission = Some(AdmissionConfig {
+            max_in_flight: Some(4),
+            rate_limit: None,
+            adaptive: None,
+        });
+        let result = run_simulation(&config).expect("simulation should succeed");
+
+        let admission = result.phase1_metrics.admission.expect("admission metrics");
+        assert_eq!(admission.admitted, 4);
+        assert_eq!(admission.rejected_max_in_flight, 6);
+    }
+
+    #[test]
+    fn admission_counts_only_measured_requests() {
+        let mut config = config_with_servers(vec![ServerConfig::new("a", 2)]);
+        // One request in flight at a time: arrivals at even times are
+        // admitted and the odd ones in between rejected.
+        config.requests = RequestProfile::FixedCount(10);
+        config.admission = Some(AdmissionConfig {
+            max_in_flight: Some(1),
+            rate_limit: None,
+            adaptive: None,
+        });
+        config.measurement = Some(MeasurementConfig {
+            warmup_ms: 4,
+            window_ms: None,
+        });
+        let result = run_simulation(&config).expect("simulation should succeed");
+
+        let admission = result.phase1_metrics.admission.expect("admission metrics");
+        assert_eq!(admission.admitted, 3);
+        assert_eq!(admission.rejected, 3);
+        assert_eq!(admission.rejected_fraction, 0.5);
+    }
+
+    struct LastServerStrategy;
+
+    impl SelectionStrategy for LastServerStrategy {
+        fn select(&mut self, ctx: &mut SelectionContext) -> crate::algorithms::Selection {
+            crate::algorithms::Selection {
+                server_id: ServerId::from(ctx.servers.len() - 1),
+                score: None,
+            }
+        }
+    }
+
+    #[test]
+    fn custom_registry_runs_registered_strategy() {
+        let mut registry = StrategyRegistry::builtin();
+        registry
+            .register(crate::algorithms::StrategyRegistration::new(
+                "last-server",
+                "Always routes to the last server.",
+                |_| Box::new(LastServerStrategy),
+            ))
+            .expect("name is free");
+        let mut config = overload_config(None);
+        config.algo = AlgoConfig::new("last-server");
+
+        let result = run_simulation_with_registry(&config, &registry, true)
+            .expect("simulation should succeed");
+        assert_eq!(result.totals[0].requests, 0);
+        assert_eq!(result.totals[1].requests as usize, result.assignments.len());
+
+        assert!(matches!(
+            run_simulation(&config),
+            Err(Error::UnknownAlgorithm(name)) if name == "last-server"
+        ));
+    }
+
+    struct FailingStrategy;
+
+    impl SelectionStrategy for FailingStrategy {
+        fn select(&mut self, _ctx: &mut SelectionContext) -> crate::algorithms::Selection {
+            crate::algorithms::Selection {
+                server_id: ServerId::from(0),
+                score: None,
+            }
+        }
+
+        fn take_error(&mut self) -> Option<String> {
+            Some("no route".to_string())
+        }
+    }
+
+    #[test]
+    fn strategy_error_aborts_the_run() {
+        let mut registry = StrategyRegistry::new();
+        registry
+            .register(crate::algorithms::StrategyRegistration::new(
+                "failing",
+                "Fails every selection.",
+                |_| Box::new(FailingStrategy),
+            ))
+            .expect("name is free");
+        let mut config = overload_config(None);
+        config.algo = AlgoConfig::new("failing");
+        config.sessions = Some(SessionConfig {
+            mean_length: 2.0,
+            length: SessionLength::Fixed,
+            concurrent: 2,
+            max_in_flight: None,
+        });
+
+        assert!(matches!(
+            run_simulation_with_registry(&config, &registry, true),
+            Err(Error::StrategyFailed(message)) if message == "no route"
+        ));
+    }
 }
@@ -12,6 +12,30 @@ const ERR_INVALID_WEIGHT_VALUE: &str = "weight must be > 0 in";
 const ERR_INVALID_REQUEST_RATE: &str = "request rate must be > 0";
 const ERR_INVALID_REQUEST_DURATION: &str = "request duration must be > 0";
 const ERR_INVALID_TIE_BREAK_SEED: &str = "tie-break seed required when tie_break is seeded";
+const ERR_BALANCERS_ZERO: &str = "balancer count must be greater than 0";
+const ERR_INVALID_SYNC_INTERVAL: &str = "sync interval must be > 0";
+const ERR_SYNC_DELAY_WITHOUT_INTERVAL: &str = "sync delay requires a sync interval";
+const ERR_UNKNOWN_ZONE: &str = "no servers in zone";
+const ERR_INVALID_SPILLOVER_THRESHOLD: &str = "spillover threshold must be in (0, 1]";
+const ERR_INVALID_OUTAGE: &str = "outage must end after it starts in";
+const ERR_OVERLAPPING_OUTAGES: &str = "outages overlap or touch in";
+const ERR_INVALID_HEALTHY_THRESHOLD: &str = "healthy threshold must be in (0, 1]";
+const ERR_SESSIONS_ZERO: &str = "concurrent sessions must be greater than 0";
+const ERR_INVALID_SESSION_LENGTH: &str = "mean session length must be >= 1";
+const ERR_KEYS_ZERO: &str = "key count must be greater than 0";
+const ERR_INVALID_ZIPF_EXPONENT: &str = "zipf exponent must be >= 0";
+const ERR_CACHE_CAPACITY_ZERO: &str = "cache capacity must be greater than 0";
+const ERR_CACHE_WITHOUT_KEYS: &str = "cache model requires request keys";
+const ERR_INVALID_ADMISSION: &str = "invalid admission control";
+const ERR_MEASUREMENT_WINDOW_ZERO: &str = "measurement window must be > 0";
+const ERR_INVALID_SIGNIFICANT_DIGITS: &str = "significant digits must be in 1..=5";
+const ERR_UNKNOWN_ALGORITHM: &str = "unknown algorithm";
+const ERR_DUPLICATE_ALGORITHM: &str = "algorithm already registered";
+const ERR_INVALID_ALGO_PARAM: &str = "invalid parameter for";
+const ERR_SCRIPT: &str = "invalid strategy script";
+const ERR_PLUGIN: &str = "invalid strategy plugin";
+const ERR_STRATEGY_FAILED: &str = "strategy failed";
+const ERR_INVALID_SWEEP_AXIS: &str = "invalid sweep axis";
 const ERR_UNSUPPORTED_CONFIG_FORMAT: &str = "unsupported config format";
 
 #[derive(Error, Debug)]
@@ -40,10 +64,62 @@ pub enum Error {
     InvalidRequestDuration(u64),
     #[error("{ERR_INVALID_TIE_BREAK_SEED}")]
     InvalidTieBreakSeed,
+    #[error("{ERR_BALANCERS_ZERO}")]
+    BalancersZero,
+    #[error("{ERR_INVALID_SYNC_INTERVAL} (got {0}ms)")]
+    InvalidSyncInterval(u64),
+    #[error("{ERR_SYNC_DELAY_WITHOUT_INTERVAL}")]
+    SyncDelayWithoutInterval,
+    #[error("{ERR_UNKNOWN_ZONE} '{0}'")]
+    UnknownZone(String),
+    #[error("{ERR_INVALID_SPILLOVER_THRESHOLD} (got {0})")]
+    InvalidSpilloverThreshold(f64),
+    #[error("{ERR_INVALID_OUTAGE} '{0}'")]
+    InvalidOutage(String),
+    #[error("{ERR_OVERLAPPING_OUTAGES} '{0}'")]
+    OverlappingOutages(String),
+    #[error("{ERR_INVALID_HEALTHY_THRESHOLD} (got {0})")]
+    InvalidHealthyThreshold(f64),
+    #[error("{ERR_SESSIONS_ZERO}")]
+    SessionsZero,
+    #[error("{ERR_INVALID_SESSION_LENGTH} (got {0})")]
+    InvalidSessionLength(f64),
+    #[error("{ERR_KEYS_ZERO}")]
+    KeysZero,
+    #[error("{ERR_INVALID_ZIPF_EXPONENT} (got {0})")]
+    InvalidZipfExponent(f64),
+    #[error("{ERR_CACHE_CAPACITY_ZERO}")]
+    CacheCapacityZero,
+    #[error("{ERR_CACHE_WITHOUT_KEYS}")]
+    CacheWithoutKeys,
+    #[error("{ERR_INVALID_ADMISSION}: {0}")]
+    InvalidAdmission(String),
+    #[error("{ERR_MEASUREMENT_WINDOW_ZERO}")]
+    MeasurementWindowZero,
+    #[error("{ERR_INVALID_SIGNIFICANT_DIGITS} (got {0})")]
+    InvalidSignificantDigits(u8),
+    #[error("{ERR_UNKNOWN_ALGORITHM} '{0}' (see list-algorithms)")]
+    UnknownAlgorithm(String),
+    #[error("{ERR_DUPLICATE_ALGORITHM} '{0}'")]
+    DuplicateAlgorithm(String),
+    #[error("{ERR_INVALID_ALGO_PARAM} '{0}': {1}")]
+    InvalidAlgoParam(String, String),
+    #[error("{ERR_SCRIPT}: {0}")]
+    Script(String),
+    #[error("{ERR_PLUGIN}: {0}")]
+    Plugin(String),
+    #[error("{ERR_STRATEGY_FAILED}: {0}")]
+    StrategyFailed(String),
+    #[error("{ERR_INVALID_SWEEP_AXIS} '{0}': {1}")]
+    InvalidSweepAxis(String, String),
     #[error("{0}")]
     ConfigIo(String),
     #[error("{0}")]
     ConfigParse(String),
+    #[error("{0}")]
+    EventLog(String),
+    #[error("{0}")]
+    TimeSeriesIo(String),
     #[error("{ERR_UNSUPPORTED_CONFIG_FORMAT} '{0}'")]
     UnsupportedConfigFormat(String),
     #[error("{0}")]
@@ -0,0 +1,127 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::error::{Error, Result};
+use crate::state::RejectReason;
+
+/// What an event-log line records. Rejections are logged for arrivals that
+/// admission control turned away instead of routing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    RequestArrival,
+    RequestRejected,
+    RequestComplete,
+    /// Lost to an outage: queued on a server that went down, or arrived
+    /// while every server was down.
+    RequestFailed,
+    ServerDown,
+    ServerUp,
+    SyncTick,
+    ViewSync,
+}
+
+/// One processed event; fields that do not apply to `kind` are omitted.
+#[derive(Clone, Debug, Serialize)]
+pub struct EventRecord<'a> {
+    pub time_ms: u64,
+    pub kind: EventKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balancer_id: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_id: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server: Option<&'a str>,
+    /// The strategy's score for the chosen server, if it reports one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<RejectReason>,
+    /// Sync tick number, or the snapshot a view sync applies.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tick: Option<usize>,
+}
+
+impl EventRecord<'_> {
+    pub fn new(time_ms: u64, kind: EventKind) -> Self {
+        Self {
+            time_ms,
+            kind,
+            request_id: None,
+            balancer_id: None,
+            server_id: None,
+            server: None,
+            score: None,
+            session_id: None,
+            key: None,
+            started_at: None,
+            completed_at: None,
+            latency_ms: None,
+            reason: None,
+            tick: None,
+        }
+    }
+}
+
+/// Streams event records as newline-delimited JSON while the engine runs.
+pub struct EventLog {
+    writer: Box<dyn Write>,
+    name: String,
+}
+
+impl EventLog {
+    pub fn create(path: &Path) -> Result<Self> {
+        let file = File::create(path).map_err(|err| {
+            Error::EventLog(format!(
+                "failed to create event log '{}': {}",
+                path.display(),
+                err
+            ))
+        })?;
+        Ok(Self::from_writer(
+            BufWriter::new(file),
+            path.display().to_string(),
+        ))
+    }
+
+    /// Logs to `writer`; `name` identifies it in error messages.
+    pub fn from_writer(writer: impl Write + 'static, name: impl Into<String>) -> Self {
+        Self {
+            writer: Box::new(writer),
+            name: name.into(),
+        }
+    }
+
+    pub fn write(&mut self, record: &EventRecord) -> Result<()> {
+        serde_json::to_writer(&mut self.writer, record)
+            .map_err(std::io::Error::from)
+            .and_then(|()| self.writer.write_all(b"\n"))
+            .map_err(|err| self.write_error(err))
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush().map_err(|err| self.write_error(err))
+    }
+
+    fn write_error(&self, err: std::io::Error) -> Error {
+        Error::EventLog(format!(
+            "failed to write event log '{}': {}",
+            self.name, err
+        ))
+    }
+}
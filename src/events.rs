@@ -6,6 +6,10 @@ use crate::state::ServerId;
 pub struct Request {
     pub id: usize,
     pub arrival_time_ms: u64,
+    pub session_id: Option<u64>,
+    /// Last request of its session: the session's pin is dropped once it is routed.
+    pub session_end: bool,
+    pub key: Option<u64>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -14,6 +18,21 @@ pub enum Event {
     RequestComplete {
         server_id: ServerId,
         request_id: usize,
+        balancer_id: usize,
+        /// Response time observed by the balancer, from arrival to completion.
+        latency_ms: u64,
+    },
+    ServerDown {
+        server_id: ServerId,
+    },
+    ServerUp {
+        server_id: ServerId,
+    },
+    SyncTick {
+        tick: usize,
+    },
+    ViewSync {
+        snapshot_id: usize,
     },
 }
 
@@ -48,7 +67,10 @@ impl Event {
     fn priority(&self) -> u8 {
         match self {
             Event::RequestComplete { .. } => 0,
-            Event::RequestArrival(_) => 1,
+            Event::ServerDown { .. } | Event::ServerUp { .. } => 1,
+            Event::ViewSync { .. } => 2,
+            Event::SyncTick { .. } => 3,
+            Event::RequestArrival(_) => 4,
         }
     }
 
@@ -56,6 +78,11 @@ impl Event {
         match self {
             Event::RequestComplete { request_id, .. } => *request_id,
             Event::RequestArrival(request) => request.id,
+            Event::ServerDown { server_id } | Event::ServerUp { server_id } => {
+                usize::from(*server_id)
+            }
+            Event::SyncTick { tick } => *tick,
+            Event::ViewSync { snapshot_id } => *snapshot_id,
         }
     }
 }
@@ -0,0 +1,238 @@
+use crate::models::{DistributionKind, LatencyBackend, MetricsConfig};
+use crate::state::{CdfPoint, HistogramBucket, ResponseTimeDistribution};
+
+/// Collects response times and answers percentile and distribution queries,
+/// either exactly or from a bounded-memory histogram.
+#[derive(Clone, Debug)]
+pub enum LatencyRecorder {
+    /// Every response time; O(n) memory, exact nearest-rank percentiles.
+    Exact(Vec<u64>),
+    Hdr(HdrHistogram),
+}
+
+impl LatencyRecorder {
+    pub fn new(config: &MetricsConfig) -> Self {
+        match config.backend {
+            LatencyBackend::Exact => LatencyRecorder::Exact(Vec::new()),
+            LatencyBackend::Hdr => {
+                LatencyRecorder::Hdr(HdrHistogram::new(config.significant_digits))
+            }
+        }
+    }
+
+    pub fn record(&mut self, response_ms: u64) {
+        match self {
+            LatencyRecorder::Exact(values) => values.push(response_ms),
+            LatencyRecorder::Hdr(histogram) => histogram.record(response_ms),
+        }
+    }
+
+    /// Sorts the exact samples; call once recording is done.
+    pub fn finish(&mut self) {
+        if let LatencyRecorder::Exact(values) = self {
+            values.sort_unstable();
+        }
+    }
+
+    /// Nearest-rank percentile, `None` when nothing was recorded. Expects
+    /// `finish` to have been called.
+    pub fn percentile(&self, percentile: f64) -> Option<u64> {
+        match self {
+            LatencyRecorder::Exact(sorted) => {
+                let rank = nearest_rank(percentile, sorted.len() as u64)?;
+                Some(sorted[rank as usize - 1])
+            }
+            LatencyRecorder::Hdr(histogram) => histogram.percentile(percentile),
+        }
+    }
+
+    /// Histogram or CDF over the distinct recorded values (histogram buckets
+    /// for the HDR backend). Expects `finish` to have been called.
+    pub fn distribution(&self, kind: DistributionKind) -> ResponseTimeDistribution {
+        let buckets: Vec<HistogramBucket> = match self {
+            LatencyRecorder::Exact(sorted) => {
+                let mut buckets: Vec<HistogramBucket> = Vec::new();
+                for &response_ms in sorted {
+                    match buckets.last_mut() {
+                        Some(bucket) if bucket.response_ms == response_ms => bucket.count += 1,
+                        _ => buckets.push(HistogramBucket {
+                            response_ms,
+                            count: 1,
+                        }),
+                    }
+                }
+                buckets
+            }
+            LatencyRecorder::Hdr(histogram) => histogram
+                .buckets()
+                .map(|(response_ms, count)| HistogramBucket { response_ms, count })
+                .collect(),
+        };
+        match kind {
+            DistributionKind::Histogram => ResponseTimeDistribution::Histogram(buckets),
+            DistributionKind::Cdf => {
+                let total = buckets.iter().map(|bucket| bucket.count).sum::<u64>();
+                let mut seen = 0;
+                let points = buckets
+                    .into_iter()
+                    .map(|bucket| {
+                        seen += bucket.count;
+                        CdfPoint {
+                            response_ms: bucket.response_ms,
+                            fraction: round_to_millionths(seen as f64 / total as f64),
+                        }
+                    })
+                    .collect();
+                ResponseTimeDistribution::Cdf(points)
+            }
+        }
+    }
+}
+
+/// Log-linear histogram in the style of HdrHistogram: values below
+/// `2 * 10^digits` (rounded up to a power of two) get a bucket each, and
+/// every further power of two is split into as many buckets, so a bucket's
+/// width is at most `10^-digits` of its values. Memory depends on the value
+/// range and precision, not on the number of samples.
+#[derive(Clone, Debug)]
+pub struct HdrHistogram {
+    sub_bucket_bits: u32,
+    counts: Vec<u64>,
+    total: u64,
+    max: u64,
+}
+
+impl HdrHistogram {
+    pub fn new(significant_digits: u8) -> Self {
+        let sub_buckets = 2 * 10u64.pow(u32::from(significant_digits));
+        Self {
+            sub_bucket_bits: sub_buckets.next_power_of_two().trailing_zeros(),
+            counts: Vec::new(),
+            total: 0,
+            max: 0,
+        }
+    }
+
+    pub fn record(&mut self, value: u64) {
+        let index = self.index_of(value);
+        if index >= self.counts.len() {
+            self.counts.resize(index + 1, 0);
+        }
+        self.counts[index] += 1;
+        self.total += 1;
+        self.max = self.max.max(value);
+    }
+
+    pub fn len(&self) -> u64 {
+        self.total
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+
+    /// Highest value equivalent to the nearest-rank sample, capped at the
+    /// largest recorded value so `max` stays exact.
+    pub fn percentile(&self, percentile: f64) -> Option<u64> {
+        let rank = nearest_rank(percentile, self.total)?;
+        let mut seen = 0;
+        self.buckets()
+            .find(|&(_, count)| {
+                seen += count;
+                seen >= rank
+            })
+            .map(|(value, _)| value)
+    }
+
+    /// Non-empty buckets as (highest equivalent value, count), ascending.
+    pub fn buckets(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        self.counts
+            .iter()
+            .enumerate()
+            .filter(|(_, &count)| count > 0)
+            .map(|(index, &count)| (self.highest_equivalent(index).min(self.max), count))
+    }
+
+    fn half_sub_buckets(&self) -> u64 {
+        1 << (self.sub_bucket_bits - 1)
+    }
+
+    fn index_of(&self, value: u64) -> usize {
+        let shift = (u64::BITS - value.leading_zeros()).saturating_sub(self.sub_bucket_bits);
+        ((u64::from(shift) * self.half_sub_buckets()) + (value >> shift)) as usize
+    }
+
+    fn highest_equivalent(&self, index: usize) -> u64 {
+        let index = index as u64;
+        let half = self.half_sub_buckets();
+        let shift = (index / half).saturating_sub(1);
+        let sub_bucket = index - shift * half;
+        (sub_bucket << shift) + ((1 << shift) - 1)
+    }
+}
+
+/// 1-based nearest rank of `percentile` among `count` samples. The tolerance
+/// keeps e.g. p99.9 of 1000 samples at rank 999 despite rounding.
+fn nearest_rank(percentile: f64, count: u64) -> Option<u64> {
+    if count == 0 {
+        return None;
+    }
+    let rank = (percentile * count as f64 / 100.0 - 1e-9).ceil() as u64;
+    Some(rank.clamp(1, count))
+}
+
+fn round_to_millionths(value: f64) -> f64 {
+    (value * 1e6).round() / 1e6
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact_percentiles_use_nearest_rank() {
+        let mut recorder = LatencyRecorder::Exact(Vec::new());
+        for value in (1..=1000).rev() {
+            recorder.record(value);
+        }
+        recorder.finish();
+
+        assert_eq!(recorder.percentile(50.0), Some(500));
+        assert_eq!(recorder.percentile(99.9), Some(999));
+        assert_eq!(recorder.percentile(100.0), Some(1000));
+        assert_eq!(LatencyRecorder::Exact(Vec::new()).percentile(50.0), None);
+    }
+
+    #[test]
+    fn hdr_percentiles_stay_within_precision() {
+        let mut histogram = HdrHistogram::new(2);
+        for value in 1..=100_000u64 {
+            histogram.record(value * 7);
+        }
+
+        assert_eq!(histogram.len(), 100_000);
+        for percentile in [1.0f64, 50.0, 90.0, 99.0, 99.9] {
+            let exact = (percentile * 1000.0).round() as u64 * 7;
+            let approx = histogram.percentile(percentile).unwrap();
+            assert!(
+                approx >= exact && (approx - exact) as f64 <= exact as f64 * 0.01,
+                "p{}: {} vs {}",
+                percentile,
+                approx,
+                exact
+            );
+        }
+        assert_eq!(histogram.percentile(100.0), Some(700_000));
+        // Bucket count depends on the value range, not on the sample count.
+        assert!(histogram.counts.len() < 2_000);
+
+        let mut small = HdrHistogram::new(3);
+        for value in [3, 3, 5, 1999] {
+            small.record(value);
+        }
+        assert_eq!(
+            small.buckets().collect::<Vec<_>>(),
+            vec![(3, 2), (5, 1), (1999, 1)]
+        );
+    }
+}
@@ -1,8 +1,20 @@
+pub mod admission;
 pub mod algorithms;
+pub mod arrivals;
+pub mod cache;
+pub mod cli;
+pub mod compare;
 pub mod config;
 pub mod engine;
 pub mod error;
+pub mod event_log;
 pub mod events;
+pub mod latency;
 pub mod models;
 pub mod output;
+pub mod replication;
 pub mod state;
+pub mod sweep;
+#[cfg(test)]
+mod test_support;
+pub mod timeseries;
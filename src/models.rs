@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -10,6 +11,46 @@ pub struct SimConfig {
     pub tie_break: TieBreakConfig,
     #[serde(default)]
     pub seed: Option<u64>,
+    #[serde(default)]
+    pub balancers: Option<BalancerConfig>,
+    #[serde(default)]
+    pub locality: Option<LocalityConfig>,
+    #[serde(default)]
+    pub failover: Option<FailoverConfig>,
+    #[serde(default)]
+    pub sessions: Option<SessionConfig>,
+    #[serde(default)]
+    pub keys: Option<KeyConfig>,
+    #[serde(default)]
+    pub cache: Option<CacheConfig>,
+    #[serde(default)]
+    pub admission: Option<AdmissionConfig>,
+    #[serde(default)]
+    pub measurement: Option<MeasurementConfig>,
+    #[serde(default)]
+    pub metrics: Option<MetricsConfig>,
+}
+
+impl SimConfig {
+    /// A stable tie-break run with every optional section unset.
+    pub fn new(servers: Vec<ServerConfig>, requests: RequestProfile, algo: AlgoConfig) -> Self {
+        Self {
+            servers,
+            requests,
+            algo,
+            tie_break: TieBreakConfig::default(),
+            seed: None,
+            balancers: None,
+            locality: None,
+            failover: None,
+            sessions: None,
+            keys: None,
+            cache: None,
+            admission: None,
+            measurement: None,
+            metrics: None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -18,6 +59,220 @@ pub struct ServerConfig {
     pub base_latency_ms: u64,
     #[serde(default = "default_weight")]
     pub weight: u32,
+    /// One-way network latency between the balancer and this server.
+    #[serde(default)]
+    pub network_latency_ms: u64,
+    /// Upper bound of the uniform jitter added to each network hop.
+    #[serde(default)]
+    pub network_jitter_ms: u64,
+    #[serde(default)]
+    pub zone: Option<String>,
+    /// Failover tier; traffic prefers the lowest tier with enough healthy servers.
+    #[serde(default)]
+    pub priority: u32,
+    #[serde(default)]
+    pub outages: Vec<OutageWindow>,
+}
+
+impl ServerConfig {
+    /// A server of weight 1 with no network delay, zone, priority tier or
+    /// outages.
+    pub fn new(name: impl Into<String>, base_latency_ms: u64) -> Self {
+        Self {
+            name: name.into(),
+            base_latency_ms,
+            weight: default_weight(),
+            network_latency_ms: 0,
+            network_jitter_ms: 0,
+            zone: None,
+            priority: 0,
+            outages: Vec::new(),
+        }
+    }
+}
+
+/// Window `[start_ms, end_ms)` during which a server is down.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct OutageWindow {
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+/// Independent load-balancer instances sharing the same server pool.
+///
+/// Arrivals are split round-robin across balancers. Each balancer keeps its
+/// own strategy and its own view of server load, updated by the requests it
+/// dispatches (and their completions). When `sync_interval_ms` is set, every
+/// balancer's view is replaced with a snapshot of the true server state taken
+/// at each interval and delivered `sync_delay_ms` later.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BalancerConfig {
+    pub count: usize,
+    #[serde(default)]
+    pub sync_interval_ms: Option<u64>,
+    #[serde(default)]
+    pub sync_delay_ms: u64,
+}
+
+impl BalancerConfig {
+    pub fn label(&self) -> String {
+        match self.sync_interval_ms {
+            Some(interval) => format!(
+                "{} (sync every {}ms, delay {}ms)",
+                self.count, interval, self.sync_delay_ms
+            ),
+            None => format!("{} (no sync)", self.count),
+        }
+    }
+}
+
+/// Zone-aware routing: the balancers sit in `zone` and prefer servers in that
+/// zone until the busy fraction of local servers reaches `spillover_threshold`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LocalityConfig {
+    pub zone: String,
+    #[serde(default = "default_spillover_threshold")]
+    pub spillover_threshold: f64,
+    #[serde(default)]
+    pub spillover: SpilloverMode,
+}
+
+/// How much traffic leaves the local zone once it is past the threshold.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SpilloverMode {
+    /// Every request goes cross-zone while the threshold is reached.
+    #[default]
+    Threshold,
+    /// The cross-zone fraction ramps up with local load, up to the other
+    /// zones' share of capacity when every local server is busy.
+    Proportional,
+}
+
+impl fmt::Display for SpilloverMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            SpilloverMode::Threshold => "threshold",
+            SpilloverMode::Proportional => "proportional",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// Priority tiers: a tier keeps all traffic while its healthy fraction is at
+/// least `healthy_threshold`, otherwise traffic moves to the next tier.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FailoverConfig {
+    #[serde(default = "default_healthy_threshold")]
+    pub healthy_threshold: f64,
+}
+
+impl Default for FailoverConfig {
+    fn default() -> Self {
+        Self {
+            healthy_threshold: default_healthy_threshold(),
+        }
+    }
+}
+
+/// Sticky sessions: arrivals are spread over `concurrent` open sessions whose
+/// lengths (in requests) follow `length`. A session's first request is
+/// balanced normally; later requests stick to the same server unless it is
+/// down or has `max_in_flight` requests in flight.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SessionConfig {
+    #[serde(default = "default_concurrent_sessions")]
+    pub concurrent: usize,
+    pub mean_length: f64,
+    #[serde(default)]
+    pub length: SessionLength,
+    #[serde(default)]
+    pub max_in_flight: Option<u32>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SessionLength {
+    Fixed,
+    #[default]
+    Geometric,
+}
+
+impl fmt::Display for SessionLength {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            SessionLength::Fixed => "fixed",
+            SessionLength::Geometric => "geometric",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// Request keys (e.g. cache keys) drawn from `count` distinct values. With
+/// `zipf_exponent` > 0 key `k` (0-based rank) is drawn with probability
+/// proportional to `1 / (k + 1)^zipf_exponent`; 0 means uniform.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct KeyConfig {
+    pub count: u64,
+    #[serde(default)]
+    pub zipf_exponent: f64,
+}
+
+/// Per-server LRU cache of request keys. Each server caches up to `capacity`
+/// keys; a hit is served in `hit_latency_ms` (capped at the server's
+/// `base_latency_ms`) and a miss takes the full `base_latency_ms`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CacheConfig {
+    pub capacity: usize,
+    #[serde(default = "default_cache_hit_latency_ms")]
+    pub hit_latency_ms: u64,
+}
+
+/// Admission control applied before routing, shared by all load balancers.
+/// Rejected requests are never dispatched and are reported separately.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AdmissionConfig {
+    /// Reject while this many admitted requests are still in flight.
+    #[serde(default)]
+    pub max_in_flight: Option<u32>,
+    #[serde(default)]
+    pub rate_limit: Option<TokenBucketConfig>,
+    #[serde(default)]
+    pub adaptive: Option<AdaptiveLimitConfig>,
+}
+
+/// Token bucket refilled at `rate` tokens per second, holding at most `burst`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TokenBucketConfig {
+    pub rate: f64,
+    #[serde(default = "default_rate_limit_burst")]
+    pub burst: u32,
+}
+
+/// AIMD concurrency limit driven by the response times the balancer observes.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AdaptiveLimitConfig {
+    pub latency_threshold_ms: u64,
+    #[serde(default = "default_initial_concurrency_limit")]
+    pub initial_limit: u32,
+    #[serde(default = "default_min_concurrency_limit")]
+    pub min_limit: u32,
+    #[serde(default = "default_max_concurrency_limit")]
+    pub max_limit: u32,
+    #[serde(default = "default_concurrency_backoff")]
+    pub backoff: f64,
+}
+
+impl AdaptiveLimitConfig {
+    pub fn with_threshold(latency_threshold_ms: u64) -> Self {
+        Self {
+            latency_threshold_ms,
+            initial_limit: default_initial_concurrency_limit(),
+            min_limit: default_min_concurrency_limit(),
+            max_limit: default_max_concurrency_limit(),
+            backoff: default_concurrency_backoff(),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -28,28 +283,409 @@ pub enum RequestProfile {
     Burst { count: usize, at_ms: u64 },
 }
 
+impl RequestProfile {
+    /// When the arrival process starts: `at_ms` for a burst, 0 otherwise.
+    pub fn start_ms(&self) -> u64 {
+        match self {
+            RequestProfile::Burst { at_ms, .. } => *at_ms,
+            RequestProfile::FixedCount(_) | RequestProfile::Poisson { .. } => 0,
+        }
+    }
+}
+
+/// Window of arrivals the metrics are computed over, starting `warmup_ms`
+/// after the arrival process starts. Requests arriving outside it still run
+/// but are left out of latency percentiles, wait and network averages,
+/// utilization, throughput, and fairness.
 #[derive(Clone, Debug, Deserialize, Serialize)]
-#[serde(rename_all = "kebab-case")]
-pub enum AlgoConfig {
-    RoundRobin,
-    WeightedRoundRobin,
-    LeastConnections,
-    LeastResponseTime,
+pub struct MeasurementConfig {
+    #[serde(default)]
+    pub warmup_ms: u64,
+    /// Length of the window; open-ended when absent.
+    #[serde(default)]
+    pub window_ms: Option<u64>,
+}
+
+impl MeasurementConfig {
+    pub fn label(&self) -> String {
+        match self.window_ms {
+            Some(window_ms) => format!("warmup {}ms, window {}ms", self.warmup_ms, window_ms),
+            None => format!("warmup {}ms", self.warmup_ms),
+        }
+    }
+}
+
+/// Selection strategy to run, looked up by name in a `StrategyRegistry` (the
+/// built-in one unless a custom registry is passed), plus the strategy's own
+/// parameters. Written either as a bare name, `algo = "round-robin"`, or as a
+/// table, `algo = { kind = "join-shortest-queue", d = 3 }`.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(from = "AlgoConfigRepr", into = "AlgoConfigRepr")]
+pub struct AlgoConfig {
+    name: String,
+    params: BTreeMap<String, ParamValue>,
+}
+
+impl AlgoConfig {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            params: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_param(mut self, name: impl Into<String>, value: impl Into<ParamValue>) -> Self {
+        self.set_param(name, value);
+        self
+    }
+
+    pub fn set_param(&mut self, name: impl Into<String>, value: impl Into<ParamValue>) {
+        self.params.insert(name.into(), value.into());
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn param(&self, name: &str) -> Option<&ParamValue> {
+        self.params.get(name)
+    }
+
+    pub fn params(&self) -> &BTreeMap<String, ParamValue> {
+        &self.params
+    }
 }
 
 impl fmt::Display for AlgoConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if !self.params.is_empty() {
+            let params: Vec<String> = self
+                .params
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect();
+            write!(f, " ({})", params.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum AlgoConfigRepr {
+    Name(String),
+    Table {
+        kind: String,
+        #[serde(flatten)]
+        params: BTreeMap<String, ParamValue>,
+    },
+}
+
+impl From<AlgoConfigRepr> for AlgoConfig {
+    fn from(repr: AlgoConfigRepr) -> Self {
+        match repr {
+            AlgoConfigRepr::Name(name) => Self::new(name),
+            AlgoConfigRepr::Table { kind, params } => Self { name: kind, params },
+        }
+    }
+}
+
+impl From<AlgoConfig> for AlgoConfigRepr {
+    fn from(algo: AlgoConfig) -> Self {
+        if algo.params.is_empty() {
+            AlgoConfigRepr::Name(algo.name)
+        } else {
+            AlgoConfigRepr::Table {
+                kind: algo.name,
+                params: algo.params,
+            }
+        }
+    }
+}
+
+/// Value of one strategy parameter. Integers are accepted wherever a float is
+/// expected.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ParamValue {
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+}
+
+impl ParamValue {
+    /// Parses a command-line value as a bool, integer or float, falling back
+    /// to a string.
+    pub fn parse(value: &str) -> Self {
+        if let Ok(flag) = value.parse() {
+            ParamValue::Bool(flag)
+        } else if let Ok(integer) = value.parse() {
+            ParamValue::Integer(integer)
+        } else if let Ok(float) = value.parse() {
+            ParamValue::Float(float)
+        } else {
+            ParamValue::String(value.to_string())
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            ParamValue::Integer(value) => Some(*value as f64),
+            ParamValue::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            ParamValue::Integer(value) => u64::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            ParamValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            ParamValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ParamValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamValue::Bool(value) => write!(f, "{}", value),
+            ParamValue::Integer(value) => write!(f, "{}", value),
+            ParamValue::Float(value) => write!(f, "{}", value),
+            ParamValue::String(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl From<bool> for ParamValue {
+    fn from(value: bool) -> Self {
+        ParamValue::Bool(value)
+    }
+}
+
+impl From<i64> for ParamValue {
+    fn from(value: i64) -> Self {
+        ParamValue::Integer(value)
+    }
+}
+
+impl From<f64> for ParamValue {
+    fn from(value: f64) -> Self {
+        ParamValue::Float(value)
+    }
+}
+
+impl From<&str> for ParamValue {
+    fn from(value: &str) -> Self {
+        ParamValue::String(value.to_string())
+    }
+}
+
+/// Which response-time statistics the metrics report. Without it, runs report
+/// p95 and p99 and no distribution.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MetricsConfig {
+    #[serde(default = "default_percentiles")]
+    pub percentiles: Vec<Percentile>,
+    /// Also report every observed response time as a histogram or CDF.
+    #[serde(default)]
+    pub distribution: Option<DistributionKind>,
+    #[serde(default)]
+    pub backend: LatencyBackend,
+    /// Precision of the `hdr` backend: percentiles are within
+    /// `10^-significant_digits` of the exact value (relative).
+    #[serde(default = "default_significant_digits")]
+    pub significant_digits: u8,
+    /// Also sample every server's load over time.
+    #[serde(default)]
+    pub timeseries: Option<TimeSeriesSampling>,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            percentiles: default_percentiles(),
+            distribution: None,
+            backend: LatencyBackend::default(),
+            significant_digits: default_significant_digits(),
+            timeseries: None,
+        }
+    }
+}
+
+/// How response times are kept for percentiles and distributions.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum LatencyBackend {
+    /// Every response time, sorted at the end: exact, O(n) memory.
+    #[default]
+    Exact,
+    /// Log-linear HDR histogram: bounded memory, configurable precision.
+    Hdr,
+}
+
+impl fmt::Display for LatencyBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            LatencyBackend::Exact => "exact",
+            LatencyBackend::Hdr => "hdr",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// A response-time percentile in (0, 100], written `p50`, `p99.9`, a bare
+/// number, or `max` for p100.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(try_from = "ParamValue", into = "String")]
+pub struct Percentile(f64);
+
+impl Percentile {
+    pub const MAX: Percentile = Percentile(100.0);
+
+    pub fn new(value: f64) -> Option<Self> {
+        (value > 0.0 && value <= 100.0).then_some(Percentile(value))
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        if text.eq_ignore_ascii_case("max") {
+            return Some(Percentile::MAX);
+        }
+        let number = text.strip_prefix(['p', 'P']).unwrap_or(text);
+        number.parse().ok().and_then(Percentile::new)
+    }
+
+    pub fn value(self) -> f64 {
+        self.0
+    }
+}
+
+impl fmt::Display for Percentile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if *self == Percentile::MAX {
+            write!(f, "max")
+        } else {
+            write!(f, "p{}", self.0)
+        }
+    }
+}
+
+impl TryFrom<ParamValue> for Percentile {
+    type Error = String;
+
+    fn try_from(value: ParamValue) -> std::result::Result<Self, Self::Error> {
+        let percentile = match &value {
+            ParamValue::String(text) => Percentile::parse(text),
+            other => other.as_f64().and_then(Percentile::new),
+        };
+        percentile
+            .ok_or_else(|| format!("invalid percentile '{}': expected p0..p100 or max", value))
+    }
+}
+
+impl From<Percentile> for String {
+    fn from(percentile: Percentile) -> Self {
+        percentile.to_string()
+    }
+}
+
+/// When the per-server time series is sampled: at a fixed interval in ms
+/// (`10`) or whenever a server's load changes (`events`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "ParamValue", into = "String")]
+pub enum TimeSeriesSampling {
+    Interval(u64),
+    Events,
+}
+
+impl TimeSeriesSampling {
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        if text.eq_ignore_ascii_case("events") {
+            return Some(TimeSeriesSampling::Events);
+        }
+        let interval_ms = text.strip_suffix("ms").unwrap_or(text).parse().ok()?;
+        (interval_ms > 0).then_some(TimeSeriesSampling::Interval(interval_ms))
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            TimeSeriesSampling::Interval(interval_ms) => format!("every {}ms", interval_ms),
+            TimeSeriesSampling::Events => "on every change".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for TimeSeriesSampling {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeSeriesSampling::Interval(interval_ms) => write!(f, "{}", interval_ms),
+            TimeSeriesSampling::Events => write!(f, "events"),
+        }
+    }
+}
+
+impl TryFrom<ParamValue> for TimeSeriesSampling {
+    type Error = String;
+
+    fn try_from(value: ParamValue) -> std::result::Result<Self, Self::Error> {
+        let sampling = match &value {
+            ParamValue::String(text) => TimeSeriesSampling::parse(text),
+            other => other
+                .as_u64()
+                .filter(|&interval_ms| interval_ms > 0)
+                .map(TimeSeriesSampling::Interval),
+        };
+        sampling.ok_or_else(|| {
+            format!(
+                "invalid time series sampling '{}': expected an interval in ms or events",
+                value
+            )
+        })
+    }
+}
+
+impl From<TimeSeriesSampling> for String {
+    fn from(sampling: TimeSeriesSampling) -> Self {
+        sampling.to_string()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum DistributionKind {
+    /// Count of responses per observed response time.
+    Histogram,
+    /// Fraction of responses at or below each observed response time.
+    Cdf,
+}
+
+impl fmt::Display for DistributionKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let label = match self {
-            AlgoConfig::RoundRobin => "round-robin",
-            AlgoConfig::WeightedRoundRobin => "weighted-round-robin",
-            AlgoConfig::LeastConnections => "least-connections",
-            AlgoConfig::LeastResponseTime => "least-response-time",
+            DistributionKind::Histogram => "histogram",
+            DistributionKind::Cdf => "cdf",
         };
         write!(f, "{}", label)
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, Default)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Default, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 pub enum TieBreakConfig {
     #[default]
@@ -69,6 +705,52 @@ impl TieBreakConfig {
     }
 }
 
+pub fn default_percentiles() -> Vec<Percentile> {
+    vec![Percentile(95.0), Percentile(99.0)]
+}
+
+pub fn default_significant_digits() -> u8 {
+    3
+}
+
 fn default_weight() -> u32 {
     1
 }
+
+pub fn default_spillover_threshold() -> f64 {
+    1.0
+}
+
+pub fn default_concurrent_sessions() -> usize {
+    16
+}
+
+/// Matches Envoy's default overprovisioning factor of 1.4.
+pub fn default_healthy_threshold() -> f64 {
+    1.0 / 1.4
+}
+
+pub fn default_cache_hit_latency_ms() -> u64 {
+    1
+}
+
+pub fn default_rate_limit_burst() -> u32 {
+    1
+}
+
+pub fn default_initial_concurrency_limit() -> u32 {
+    20
+}
+
+pub fn default_min_concurrency_limit() -> u32 {
+    1
+}
+
+pub fn default_max_concurrency_limit() -> u32 {
+    1000
+}
+
+/// Netflix concurrency-limits' default AIMD backoff ratio.
+pub fn default_concurrency_backoff() -> f64 {
+    0.9
+}
@@ -1,5 +1,10 @@
+use crate::compare::ComparisonRow;
 use crate::config::FormatArg;
-use crate::state::{Assignment, Phase1Metrics, RunMetadata, ServerSummary, SimulationResult};
+use crate::replication::ReplicationReport;
+use crate::state::{
+    Assignment, Phase1Metrics, Rejection, RunMetadata, ServerSummary, SimulationResult, TimeSeries,
+};
+use crate::sweep::{SweepAxis, SweepRow};
 use serde::Serialize;
 
 pub trait Formatter {
@@ -15,6 +20,8 @@ fn formatter_for(format: &FormatArg) -> Box<dyn Formatter> {
         FormatArg::Human => Box::new(HumanFormatter),
         FormatArg::Summary => Box::new(SummaryFormatter),
         FormatArg::Json => Box::new(JsonFormatter),
+        FormatArg::Markdown => Box::new(MarkdownFormatter),
+        FormatArg::Csv => Box::new(CsvFormatter),
     }
 }
 
@@ -24,11 +31,22 @@ impl Formatter for HumanFormatter {
     fn write(&self, result: &SimulationResult) -> String {
         let mut output = String::new();
         write_metadata(&mut output, result);
-        output.push_str("Assignments:\n");
+        if !result.assignments.is_empty() {
+            output.push_str("Assignments:\n");
+        }
         for assignment in &result.assignments {
             write_assignment_with_totals(&mut output, assignment, &result.totals);
         }
-        write_summary(&mut output, &result.totals);
+        if !result.rejections.is_empty() {
+            output.push_str("Rejections:\n");
+        }
+        for rejection in &result.rejections {
+            output.push_str(&format!(
+                "Request {} rejected ({})\n",
+                rejection.request_id, rejection.reason
+            ));
+        }
+        write_summary(&mut output, result);
         output
     }
 }
@@ -39,7 +57,7 @@ impl Formatter for SummaryFormatter {
     fn write(&self, result: &SimulationResult) -> String {
         let mut output = String::new();
         write_metadata(&mut output, result);
-        write_summary(&mut output, &result.totals);
+        write_summary(&mut output, result);
         output
     }
 }
@@ -58,32 +76,370 @@ impl Formatter for JsonFormatter {
                 arrival_time_ms: assignment.arrival_time_ms,
                 started_at: assignment.started_at,
                 completed_at: assignment.completed_at,
+                network_ms: assignment.network_ms,
                 score: assignment.score,
             })
             .collect::<Vec<_>>();
         let json = JsonSimulationResult {
             assignments,
+            rejections: &result.rejections,
             totals: &result.totals,
             metadata: &result.metadata,
             phase1_metrics: &result.phase1_metrics,
+            timeseries: result.timeseries.as_ref(),
         };
         serde_json::to_string_pretty(&json).unwrap()
     }
 }
 
+/// The run's headline metrics as a one-row Markdown table, in the layout
+/// `compare` uses.
+pub struct MarkdownFormatter;
+
+impl Formatter for MarkdownFormatter {
+    fn write(&self, result: &SimulationResult) -> String {
+        format_comparison(&[ComparisonRow::from_result(result)], &FormatArg::Markdown)
+    }
+}
+
+/// The run's headline metrics as CSV with a header row, or the server time
+/// series (one row per sample and server) when it was recorded.
+pub struct CsvFormatter;
+
+impl Formatter for CsvFormatter {
+    fn write(&self, result: &SimulationResult) -> String {
+        format_comparison(&[ComparisonRow::from_result(result)], &FormatArg::Csv)
+    }
+}
+
+/// Formats a server time series as long-format CSV:
+/// `time_ms,server,in_flight,active_connections,busy`.
+pub fn format_timeseries(timeseries: &TimeSeries) -> String {
+    let header: Vec<String> = [
+        "time_ms",
+        "server",
+        "in_flight",
+        "active_connections",
+        "busy",
+    ]
+    .iter()
+    .map(|column| column.to_string())
+    .collect();
+    let cells: Vec<Vec<String>> = timeseries
+        .samples
+        .iter()
+        .flat_map(|sample| {
+            timeseries
+                .servers
+                .iter()
+                .enumerate()
+                .map(move |(idx, server)| {
+                    vec![
+                        sample.time_ms.to_string(),
+                        server.clone(),
+                        sample.in_flight[idx].to_string(),
+                        sample.active_connections[idx].to_string(),
+                        u8::from(sample.busy[idx]).to_string(),
+                    ]
+                })
+        })
+        .collect();
+    write_csv(&header, &cells)
+}
+
+/// Formats comparison rows as an aligned table (`human`, `summary`), a
+/// Markdown table, CSV, or JSON.
+pub fn format_comparison(rows: &[ComparisonRow], format: &FormatArg) -> String {
+    match format {
+        FormatArg::Json => serde_json::to_string_pretty(rows).unwrap() + "\n",
+        _ => write_rows(format, &[], rows.iter().map(|row| (Vec::new(), row))),
+    }
+}
+
+/// Formats sweep rows like `format_comparison`, with one leading column per
+/// axis. The `algo` axis is left out since every row already names its
+/// algorithm.
+pub fn format_sweep(axes: &[SweepAxis], rows: &[SweepRow], format: &FormatArg) -> String {
+    let shown: Vec<usize> = (0..axes.len())
+        .filter(|&index| axes[index].name != "algo")
+        .collect();
+    match format {
+        FormatArg::Json => {
+            let records: Vec<SweepRecord> = rows
+                .iter()
+                .map(|row| SweepRecord {
+                    axes: AxisValues(
+                        shown
+                            .iter()
+                            .map(|&index| (axes[index].name.as_str(), &row.values[index]))
+                            .collect(),
+                    ),
+                    metrics: &row.metrics,
+                })
+                .collect();
+            serde_json::to_string_pretty(&records).unwrap() + "\n"
+        }
+        _ => {
+            let names: Vec<&str> = shown
+                .iter()
+                .map(|&index| axes[index].name.as_str())
+                .collect();
+            let rows = rows.iter().map(|row| {
+                let values = shown
+                    .iter()
+                    .map(|&index| match &row.values[index] {
+                        serde_json::Value::String(text) => text.clone(),
+                        value => value.to_string(),
+                    })
+                    .collect();
+                (values, &row.metrics)
+            });
+            write_rows(format, &names, rows)
+        }
+    }
+}
+
+/// Formats a replication report: one row per metric with its mean, standard
+/// deviation, and 95% confidence interval.
+pub fn format_replications(report: &ReplicationReport, format: &FormatArg) -> String {
+    if *format == FormatArg::Json {
+        return serde_json::to_string_pretty(report).unwrap() + "\n";
+    }
+    let header: Vec<String> = [
+        "metric",
+        "mean",
+        "std_dev",
+        "ci95_low",
+        "ci95_high",
+        "samples",
+    ]
+    .iter()
+    .map(|header| header.to_string())
+    .collect();
+    let optional = |value: Option<f64>| value.map_or("-".to_string(), |v| format!("{:.3}", v));
+    let cells: Vec<Vec<String>> = report
+        .metrics
+        .iter()
+        .map(|summary| {
+            vec![
+                summary.metric.clone(),
+                format!("{:.3}", summary.mean),
+                format!("{:.3}", summary.std_dev),
+                optional(summary.ci95_low),
+                optional(summary.ci95_high),
+                summary.samples.to_string(),
+            ]
+        })
+        .collect();
+
+    match format {
+        FormatArg::Markdown => write_markdown_table(&header, &cells, 1),
+        FormatArg::Csv => write_csv(&header, &cells),
+        _ => {
+            let mut output = format!(
+                "algo: {}\nreplications: {} (seeds {}..={})\n",
+                report.algo,
+                report.replications,
+                report.seeds.first().copied().unwrap_or_default(),
+                report.seeds.last().copied().unwrap_or_default()
+            );
+            output.push_str(&write_aligned_table(&header, &cells, 1));
+            output
+        }
+    }
+}
+
+/// A sweep row as one flat JSON object: axis values first, then metrics.
+#[derive(Serialize)]
+struct SweepRecord<'a> {
+    #[serde(flatten)]
+    axes: AxisValues<'a>,
+    #[serde(flatten)]
+    metrics: &'a ComparisonRow,
+}
+
+struct AxisValues<'a>(Vec<(&'a str, &'a serde_json::Value)>);
+
+impl Serialize for AxisValues<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().copied())
+    }
+}
+
+const COMPARISON_HEADERS: [&str; 6] = [
+    "algo",
+    "p95_ms",
+    "p99_ms",
+    "throughput_rps",
+    "jain_fairness",
+    "avg_wait_ms",
+];
+
+const COMPARISON_MARKDOWN_HEADERS: [&str; 6] = [
+    "Policy",
+    "p95 (ms)",
+    "p99 (ms)",
+    "Throughput (rps)",
+    "Fairness",
+    "Avg wait (ms)",
+];
+
+fn comparison_cells(row: &ComparisonRow) -> [String; 6] {
+    let optional = |value: Option<u64>| value.map_or("-".to_string(), |ms| ms.to_string());
+    [
+        row.algo.clone(),
+        optional(row.p95_ms),
+        optional(row.p99_ms),
+        format!("{:.2}", row.throughput_rps),
+        format!("{:.4}", row.jain_fairness),
+        row.avg_wait_ms.to_string(),
+    ]
+}
+
+/// Writes metric rows preceded by `leading` columns. The leading columns and
+/// the algorithm are left-aligned, metrics right-aligned.
+fn write_rows<'a>(
+    format: &FormatArg,
+    leading: &[&str],
+    rows: impl Iterator<Item = (Vec<String>, &'a ComparisonRow)>,
+) -> String {
+    let left_aligned = leading.len() + 1;
+    let headers = match format {
+        FormatArg::Markdown => &COMPARISON_MARKDOWN_HEADERS,
+        _ => &COMPARISON_HEADERS,
+    };
+    let header: Vec<String> = leading
+        .iter()
+        .chain(headers.iter())
+        .map(|header| header.to_string())
+        .collect();
+    let cells: Vec<Vec<String>> = rows
+        .map(|(mut values, row)| {
+            values.extend(comparison_cells(row));
+            values
+        })
+        .collect();
+
+    match format {
+        FormatArg::Markdown => write_markdown_table(&header, &cells, left_aligned),
+        FormatArg::Csv => write_csv(&header, &cells),
+        _ => write_aligned_table(&header, &cells, left_aligned),
+    }
+}
+
+fn write_aligned_table(header: &[String], cells: &[Vec<String>], left_aligned: usize) -> String {
+    let widths: Vec<usize> = header
+        .iter()
+        .enumerate()
+        .map(|(column, header)| {
+            cells
+                .iter()
+                .map(|row| row[column].len())
+                .fold(header.len(), usize::max)
+        })
+        .collect();
+
+    let mut output = String::new();
+    for line in std::iter::once(header).chain(cells.iter().map(Vec::as_slice)) {
+        let mut text = String::new();
+        for (column, (cell, width)) in line.iter().zip(&widths).enumerate() {
+            if column > 0 {
+                text.push_str("  ");
+            }
+            if column < left_aligned {
+                text.push_str(&format!("{:<width$}", cell, width = width));
+            } else {
+                text.push_str(&format!("{:>width$}", cell, width = width));
+            }
+        }
+        output.push_str(text.trim_end());
+        output.push('\n');
+    }
+    output
+}
+
+fn write_markdown_table(header: &[String], cells: &[Vec<String>], left_aligned: usize) -> String {
+    let rule: Vec<String> = header
+        .iter()
+        .enumerate()
+        .map(|(column, header)| {
+            if column < left_aligned {
+                "-".repeat(header.len())
+            } else {
+                format!("{}:", "-".repeat(header.len() - 1))
+            }
+        })
+        .collect();
+
+    let mut output = String::new();
+    for line in [header, rule.as_slice()]
+        .into_iter()
+        .chain(cells.iter().map(Vec::as_slice))
+    {
+        output.push_str(&format!("| {} |\n", line.join(" | ")));
+    }
+    output
+}
+
+fn write_csv(header: &[String], cells: &[Vec<String>]) -> String {
+    let quote = |cell: &String| {
+        if cell.contains([',', '"', '\n']) {
+            format!("\"{}\"", cell.replace('"', "\"\""))
+        } else {
+            cell.clone()
+        }
+    };
+    let mut output = String::new();
+    for line in std::iter::once(header).chain(cells.iter().map(Vec::as_slice)) {
+        let line: Vec<String> = line.iter().map(quote).collect();
+        output.push_str(&line.join(","));
+        output.push('\n');
+    }
+    output
+}
+
 fn write_metadata(output: &mut String, result: &SimulationResult) {
     output.push_str("Metadata:\n");
     output.push_str(&format!("algo: {}\n", result.metadata.algo));
     output.push_str(&format!("tie_break: {}\n", result.metadata.tie_break));
     output.push_str(&format!("duration_ms: {}\n", result.metadata.duration_ms));
+    if let Some(balancers) = &result.metadata.balancers {
+        output.push_str(&format!("balancers: {}\n", balancers));
+    }
 }
 
-fn write_summary(output: &mut String, totals: &[ServerSummary]) {
+fn write_summary(output: &mut String, result: &SimulationResult) {
     output.push_str("Summary:\n");
-    for summary in totals {
+    for summary in &result.totals {
+        let percentiles = match (summary.p50_ms, summary.p95_ms, summary.p99_ms) {
+            (Some(p50), Some(p95), Some(p99)) => format!("{}/{}/{}ms", p50, p95, p99),
+            _ => "-".to_string(),
+        };
         output.push_str(&format!(
-            "{}: {} requests (avg response: {}ms)\n",
-            summary.name, summary.requests, summary.avg_response_ms
+            "{}: {} requests (avg response: {}ms, p50/p95/p99: {}, avg wait: {}ms, avg service: {}ms, max queue: {}, busy: {}ms)\n",
+            summary.name,
+            summary.requests,
+            summary.avg_response_ms,
+            percentiles,
+            summary.avg_wait_ms,
+            summary.avg_service_ms,
+            summary.max_queue_depth,
+            summary.busy_time_ms
+        ));
+    }
+    if let Some(admission) = &result.phase1_metrics.admission {
+        output.push_str(&format!(
+            "rejected: {} requests ({} max-in-flight, {} rate-limit, {} concurrency-limit)\n",
+            admission.rejected,
+            admission.rejected_max_in_flight,
+            admission.rejected_rate_limit,
+            admission.rejected_concurrency_limit
+        ));
+    }
+    if let Some(failures) = &result.phase1_metrics.failures {
+        output.push_str(&format!(
+            "failed: {} requests ({} in flight at an outage, {} with every server down)\n",
+            failures.failed, failures.failed_in_flight, failures.dropped_no_server
         ));
     }
 }
@@ -123,15 +479,20 @@ struct JsonAssignment<'a> {
     arrival_time_ms: u64,
     started_at: u64,
     completed_at: u64,
+    network_ms: u64,
     score: Option<u64>,
 }
 
 #[derive(Serialize)]
 struct JsonSimulationResult<'a> {
     assignments: Vec<JsonAssignment<'a>>,
+    #[serde(skip_serializing_if = "<[Rejection]>::is_empty")]
+    rejections: &'a [Rejection],
     totals: &'a [ServerSummary],
     metadata: &'a RunMetadata,
     phase1_metrics: &'a Phase1Metrics,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timeseries: Option<&'a TimeSeries>,
 }
 
 #[cfg(test)]
@@ -148,22 +509,35 @@ mod tests {
                 score: Some(10),
                 started_at: 0,
                 completed_at: 10,
+                network_ms: 0,
             }],
+            rejections: Vec::new(),
             totals: vec![ServerSummary {
                 name: "api".to_string(),
                 requests: 1,
                 avg_response_ms: 10,
+                p50_ms: Some(10),
+                p95_ms: Some(10),
+                p99_ms: Some(10),
+                avg_wait_ms: 0,
+                avg_service_ms: 10,
+                max_queue_depth: 0,
+                busy_time_ms: 10,
             }],
             metadata: RunMetadata {
                 algo: "round-robin".to_string(),
                 tie_break: "stable".to_string(),
                 duration_ms: 10,
+                balancers: None,
             },
             phase1_metrics: Phase1Metrics {
-                response_time: crate::state::ResponseTimePercentiles {
-                    p95_ms: Some(10),
-                    p99_ms: Some(10),
-                },
+                response_time: crate::state::ResponseTimePercentiles::new(
+                    crate::models::default_percentiles()
+                        .into_iter()
+                        .map(|percentile| (percentile, Some(10)))
+                        .collect(),
+                ),
+                response_time_distribution: None,
                 per_server_utilization: vec![crate::state::ServerUtilization {
                     name: "api".to_string(),
                     utilization_pct: 100.0,
@@ -171,7 +545,15 @@ mod tests {
                 jain_fairness: 1.0,
                 throughput_rps: 100.0,
                 avg_wait_ms: 0,
+                avg_network_ms: 0,
+                cross_zone_fraction: None,
+                sessions: None,
+                keys: None,
+                cache: None,
+                admission: None,
+                failures: None,
             },
+            timeseries: None,
         }
     }
 
@@ -187,7 +569,7 @@ mod tests {
             "Assignments:\n",
             "Request 1 -> api (score: 10ms)\n",
             "Summary:\n",
-            "api: 1 requests (avg response: 10ms)\n",
+            "api: 1 requests (avg response: 10ms, p50/p95/p99: 10/10/10ms, avg wait: 0ms, avg service: 10ms, max queue: 0, busy: 10ms)\n",
         );
         assert_eq!(output, expected);
     }
@@ -202,7 +584,7 @@ mod tests {
             "tie_break: stable\n",
             "duration_ms: 10\n",
             "Summary:\n",
-            "api: 1 requests (avg response: 10ms)\n",
+            "api: 1 requests (avg response: 10ms, p50/p95/p99: 10/10/10ms, avg wait: 0ms, avg service: 10ms, max queue: 0, busy: 10ms)\n",
         );
         assert_eq!(output, expected);
     }
@@ -220,6 +602,7 @@ mod tests {
       "arrival_time_ms": 0,
       "started_at": 0,
       "completed_at": 10,
+      "network_ms": 0,
       "score": 10
     }
   ],
@@ -227,7 +610,14 @@ mod tests {
     {
       "name": "api",
       "requests": 1,
-      "avg_response_ms": 10
+      "avg_response_ms": 10,
+      "p50_ms": 10,
+      "p95_ms": 10,
+      "p99_ms": 10,
+      "avg_wait_ms": 0,
+      "avg_service_ms": 10,
+      "max_queue_depth": 0,
+      "busy_time_ms": 10
     }
   ],
   "metadata": {
@@ -248,7 +638,8 @@ mod tests {
     ],
     "jain_fairness": 1.0,
     "throughput_rps": 100.0,
-    "avg_wait_ms": 0
+    "avg_wait_ms": 0,
+    "avg_network_ms": 0
   }
 }"#;
         assert_eq!(output, expected);
@@ -0,0 +1,270 @@
+use std::thread;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::algorithms::StrategyRegistry;
+use crate::engine::run_simulation_with_registry;
+use crate::error::Result;
+use crate::models::{SimConfig, TieBreakConfig};
+use crate::state::{Phase1Metrics, SimulationResult};
+
+/// Two-sided 95% critical values of Student's t for 1 to 30 degrees of
+/// freedom.
+const T_CRITICAL_95: [f64; 30] = [
+    12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+    2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+    2.052, 2.048, 2.045, 2.042,
+];
+
+/// Mean, spread, and confidence interval of one metric across replications.
+#[derive(Clone, Debug, Serialize)]
+pub struct MetricSummary {
+    /// Dotted path of the `Phase1Metrics` field, with per-server entries
+    /// keyed by server name (`per_server_utilization.a.utilization_pct`).
+    pub metric: String,
+    /// Replications that reported the metric; optional metrics such as
+    /// percentiles of an empty run can be missing from some.
+    pub samples: usize,
+    pub mean: f64,
+    /// Sample standard deviation; 0 with a single sample.
+    pub std_dev: f64,
+    /// 95% confidence interval of the mean (Student's t); absent with a
+    /// single sample.
+    pub ci95_low: Option<f64>,
+    pub ci95_high: Option<f64>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ReplicationReport {
+    pub algo: String,
+    pub replications: usize,
+    pub seeds: Vec<u64>,
+    pub metrics: Vec<MetricSummary>,
+}
+
+/// Runs `config` `replications` times on parallel threads with seeds
+/// `seed, seed + 1, ...` (`seed` defaults to 0) and summarizes every
+/// `Phase1Metrics` field. Replications use seeded tie-breaks, so the result
+/// does not depend on thread scheduling.
+pub fn run_replications(
+    config: &SimConfig,
+    registry: &StrategyRegistry,
+    replications: usize,
+) -> Result<ReplicationReport> {
+    let base_seed = config.seed.unwrap_or(0);
+    let seeds: Vec<u64> = (0..replications as u64)
+        .map(|offset| base_seed.wrapping_add(offset))
+        .collect();
+    let workers = thread::available_parallelism()
+        .map_or(1, usize::from)
+        .min(replications)
+        .max(1);
+
+    let mut results: Vec<Option<Result<SimulationResult>>> =
+        (0..replications).map(|_| None).collect();
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|worker| {
+                let seeds = &seeds;
+                scope.spawn(move || {
+                    (worker..seeds.len())
+                        .step_by(workers)
+                        .map(|index| {
+                            let mut variant = config.clone();
+                            variant.seed = Some(seeds[index]);
+                            variant.tie_break = TieBreakConfig::Seeded;
+                            (
+                                index,
+                                run_simulation_with_registry(&variant, registry, false),
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        for handle in handles {
+            for (index, result) in handle.join().expect("replication thread panicked") {
+                results[index] = Some(result);
+            }
+        }
+    });
+
+    let mut samples: Vec<(String, Vec<f64>)> = Vec::new();
+    let mut algo = config.algo.to_string();
+    for result in results {
+        let result = result.expect("every replication ran")?;
+        algo = result.metadata.algo;
+        for (metric, value) in metric_values(&result.phase1_metrics) {
+            match samples.iter_mut().find(|(name, _)| *name == metric) {
+                Some((_, values)) => values.push(value),
+                None => samples.push((metric, vec![value])),
+            }
+        }
+    }
+
+    Ok(ReplicationReport {
+        algo,
+        replications,
+        seeds,
+        metrics: samples
+            .into_iter()
+            .map(|(metric, values)| summarize(metric, &values))
+            .collect(),
+    })
+}
+
+/// Every numeric field of `metrics` by dotted path, sorted by path.
+fn metric_values(metrics: &Phase1Metrics) -> Vec<(String, f64)> {
+    fn walk(prefix: &str, value: &Value, out: &mut Vec<(String, f64)>) {
+        let join = |key: &str| {
+            if prefix.is_empty() {
+                key.to_string()
+            } else {
+                format!("{}.{}", prefix, key)
+            }
+        };
+        match value {
+            Value::Number(number) => out.extend(number.as_f64().map(|n| (prefix.to_string(), n))),
+            Value::Object(map) => {
+                for (key, child) in map {
+                    // A per-server entry's name is its key, not a metric, and
+                    // a response-time distribution is not a single figure.
+                    if key != "name" && key != "response_time_distribution" {
+                        walk(&join(key), child, out);
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    let key = match item.get("name").and_then(Value::as_str) {
+                        Some(name) => name.to_string(),
+                        None => index.to_string(),
+                    };
+                    walk(&join(&key), item, out);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut out = Vec::new();
+    walk("", &serde_json::to_value(metrics).unwrap(), &mut out);
+    out
+}
+
+fn summarize(metric: String, values: &[f64]) -> MetricSummary {
+    let samples = values.len();
+    let mean = values.iter().sum::<f64>() / samples as f64;
+    if samples < 2 {
+        return MetricSummary {
+            metric,
+            samples,
+            mean,
+            std_dev: 0.0,
+            ci95_low: None,
+            ci95_high: None,
+        };
+    }
+    let variance = values
+        .iter()
+        .map(|value| (value - mean).powi(2))
+        .sum::<f64>()
+        / (samples - 1) as f64;
+    let std_dev = variance.sqrt();
+    let half_width = t_critical_95(samples - 1) * std_dev / (samples as f64).sqrt();
+    MetricSummary {
+        metric,
+        samples,
+        mean,
+        std_dev,
+        ci95_low: Some(mean - half_width),
+        ci95_high: Some(mean + half_width),
+    }
+}
+
+/// Beyond the table, rounds degrees of freedom down to the nearest
+/// tabulated value so the interval errs on the wide side.
+fn t_critical_95(degrees_of_freedom: usize) -> f64 {
+    match degrees_of_freedom {
+        0 => f64::NAN,
+        1..=30 => T_CRITICAL_95[degrees_of_freedom - 1],
+        31..=39 => 2.042,
+        40..=59 => 2.021,
+        60..=119 => 2.000,
+        _ => 1.980,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AlgoConfig, RequestProfile};
+    use crate::test_support;
+
+    fn scenario() -> SimConfig {
+        test_support::scenario(
+            RequestProfile::Poisson {
+                rate: 180.0,
+                duration_ms: 500,
+            },
+            AlgoConfig::new("least-connections"),
+        )
+    }
+
+    #[test]
+    fn replications_summarize_every_metric_per_seed() {
+        let registry = StrategyRegistry::builtin();
+        let config = scenario();
+        let report = run_replications(&config, &registry, 5).expect("replications should run");
+
+        assert_eq!(report.seeds, vec![42, 43, 44, 45, 46]);
+        let names: Vec<&str> = report.metrics.iter().map(|m| m.metric.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "avg_network_ms",
+                "avg_wait_ms",
+                "jain_fairness",
+                "per_server_utilization.a.utilization_pct",
+                "per_server_utilization.b.utilization_pct",
+                "per_server_utilization.c.utilization_pct",
+                "response_time.p95_ms",
+                "response_time.p99_ms",
+                "throughput_rps",
+            ]
+        );
+
+        let p99 = &report.metrics[7];
+        let samples: Vec<f64> = report
+            .seeds
+            .iter()
+            .map(|&seed| {
+                let mut variant = config.clone();
+                variant.seed = Some(seed);
+                variant.tie_break = TieBreakConfig::Seeded;
+                let result = run_simulation_with_registry(&variant, &registry, false).unwrap();
+                result.phase1_metrics.response_time.p99_ms().unwrap() as f64
+            })
+            .collect();
+        assert_eq!(p99.samples, 5);
+        assert!((p99.mean - samples.iter().sum::<f64>() / 5.0).abs() < 1e-9);
+        assert!(p99.std_dev > 0.0);
+        let (low, high) = (p99.ci95_low.unwrap(), p99.ci95_high.unwrap());
+        assert!(low < p99.mean && p99.mean < high);
+    }
+
+    #[test]
+    fn summary_uses_sample_deviation_and_t_interval() {
+        let summary = summarize("x".to_string(), &[1.0, 2.0, 3.0, 4.0]);
+        let std_dev = (5.0f64 / 3.0).sqrt();
+
+        assert_eq!(summary.mean, 2.5);
+        assert!((summary.std_dev - std_dev).abs() < 1e-12);
+        let half_width = 3.182 * std_dev / 2.0;
+        assert!((summary.ci95_high.unwrap() - (2.5 + half_width)).abs() < 1e-12);
+
+        let single = summarize("x".to_string(), &[7.0]);
+        assert_eq!((single.std_dev, single.ci95_low), (0.0, None));
+    }
+}
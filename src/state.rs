@@ -1,4 +1,7 @@
 use serde::Serialize;
+use std::fmt;
+
+use crate::models::Percentile;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize)]
 #[serde(transparent)]
@@ -26,12 +29,15 @@ pub struct ServerState {
     pub pick_count: u32,
     pub in_flight: u32,
     pub next_available_ms: u64,
+    pub network_latency_ms: u64,
+    pub healthy: bool,
 }
 
 #[derive(Clone, Debug)]
 pub struct EngineState {
     pub time_ms: u64,
     pub servers: Vec<ServerState>,
+    pub balancer_views: Vec<Vec<ServerState>>,
     pub assignments: Vec<Assignment>,
 }
 
@@ -42,20 +48,123 @@ pub struct Assignment {
     pub arrival_time_ms: u64,
     pub started_at: u64,
     pub completed_at: u64,
+    pub network_ms: u64,
     pub score: Option<u64>,
 }
 
+/// A request turned away by admission control before it was routed.
+#[derive(Clone, Debug, Serialize)]
+pub struct Rejection {
+    pub request_id: usize,
+    pub arrival_time_ms: u64,
+    pub reason: RejectReason,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RejectReason {
+    MaxInFlight,
+    RateLimit,
+    ConcurrencyLimit,
+}
+
+impl fmt::Display for RejectReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            RejectReason::MaxInFlight => "max-in-flight",
+            RejectReason::RateLimit => "rate-limit",
+            RejectReason::ConcurrencyLimit => "concurrency-limit",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// Per-server totals. Every field covers only measured requests, those
+/// arriving inside the measurement window (after any warm-up).
 #[derive(Clone, Debug, Serialize)]
 pub struct ServerSummary {
     pub name: String,
     pub requests: u32,
     pub avg_response_ms: u64,
+    /// Nearest-rank percentiles of the server's measured response times;
+    /// `None` when none of its requests was measured.
+    pub p50_ms: Option<u64>,
+    pub p95_ms: Option<u64>,
+    pub p99_ms: Option<u64>,
+    /// Average time measured requests queued at the server before service.
+    pub avg_wait_ms: u64,
+    pub avg_service_ms: u64,
+    /// Most requests seen queued at the server (including the arriving one)
+    /// when a measured request reached it.
+    pub max_queue_depth: u32,
+    /// Total service time of measured requests, as used for utilization.
+    pub busy_time_ms: u64,
 }
 
-#[derive(Clone, Debug, Serialize)]
+/// Configured response-time percentiles (nearest rank), serialized in order
+/// as `{"p50_ms": .., "p99.9_ms": .., "max_ms": ..}`. A percentile is `None`
+/// when no request was measured.
+#[derive(Clone, Debug, Default)]
 pub struct ResponseTimePercentiles {
-    pub p95_ms: Option<u64>,
-    pub p99_ms: Option<u64>,
+    values: Vec<(Percentile, Option<u64>)>,
+}
+
+impl ResponseTimePercentiles {
+    pub fn new(values: Vec<(Percentile, Option<u64>)>) -> Self {
+        Self { values }
+    }
+
+    /// The value of `percentile`, or `None` if it was not configured or no
+    /// request was measured.
+    pub fn get(&self, percentile: f64) -> Option<u64> {
+        self.values
+            .iter()
+            .find(|(configured, _)| configured.value() == percentile)
+            .and_then(|(_, value)| *value)
+    }
+
+    pub fn p95_ms(&self) -> Option<u64> {
+        self.get(95.0)
+    }
+
+    pub fn p99_ms(&self) -> Option<u64> {
+        self.get(99.0)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Percentile, Option<u64>)> + '_ {
+        self.values.iter().copied()
+    }
+}
+
+impl Serialize for ResponseTimePercentiles {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(
+            self.values
+                .iter()
+                .map(|(percentile, value)| (format!("{}_ms", percentile), value)),
+        )
+    }
+}
+
+/// Every measured response time, as requested by `MetricsConfig::distribution`.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseTimeDistribution {
+    Histogram(Vec<HistogramBucket>),
+    Cdf(Vec<CdfPoint>),
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct HistogramBucket {
+    pub response_ms: u64,
+    pub count: u64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct CdfPoint {
+    pub response_ms: u64,
+    /// Fraction of measured responses taking at most `response_ms`.
+    pub fraction: f64,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -67,10 +176,81 @@ pub struct ServerUtilization {
 #[derive(Clone, Debug, Serialize)]
 pub struct Phase1Metrics {
     pub response_time: ResponseTimePercentiles,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_time_distribution: Option<ResponseTimeDistribution>,
     pub per_server_utilization: Vec<ServerUtilization>,
     pub jain_fairness: f64,
     pub throughput_rps: f64,
     pub avg_wait_ms: u64,
+    pub avg_network_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cross_zone_fraction: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sessions: Option<SessionMetrics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keys: Option<KeyMetrics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache: Option<CacheMetrics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub admission: Option<AdmissionMetrics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failures: Option<FailureMetrics>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SessionMetrics {
+    pub sessions: usize,
+    pub stickiness_breaks: u64,
+    /// Busiest server's request count divided by the mean per server.
+    pub load_imbalance: f64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct KeyMetrics {
+    pub distinct_keys: usize,
+    /// Fraction of repeated keys served by the same server as the key's
+    /// previous request.
+    pub key_locality: f64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct CacheMetrics {
+    pub hit_ratio: f64,
+    pub per_server: Vec<ServerCacheStats>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ServerCacheStats {
+    pub name: String,
+    pub lookups: u64,
+    pub hits: u64,
+    pub hit_ratio: f64,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AdmissionMetrics {
+    pub admitted: u64,
+    pub rejected: u64,
+    pub rejected_fraction: f64,
+    pub rejected_max_in_flight: u64,
+    pub rejected_rate_limit: u64,
+    pub rejected_concurrency_limit: u64,
+    /// Adaptive concurrency limit at the end of the run, shared by all
+    /// balancers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub final_concurrency_limit: Option<f64>,
+}
+
+/// Measured requests lost to outages, reported when any server has one.
+#[derive(Clone, Debug, Serialize)]
+pub struct FailureMetrics {
+    pub failed: u64,
+    /// Queued or in service on a server when it went down.
+    pub failed_in_flight: u64,
+    /// Arrived while every server was down.
+    pub dropped_no_server: u64,
+    /// Failed requests as a fraction of measured requests.
+    pub failed_fraction: f64,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -78,12 +258,37 @@ pub struct RunMetadata {
     pub algo: String,
     pub tie_break: String,
     pub duration_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balancers: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize)]
 pub struct SimulationResult {
     pub assignments: Vec<Assignment>,
+    pub rejections: Vec<Rejection>,
     pub totals: Vec<ServerSummary>,
     pub metadata: RunMetadata,
     pub phase1_metrics: Phase1Metrics,
+    /// Per-server load over the whole run, when `MetricsConfig::timeseries`
+    /// is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeseries: Option<TimeSeries>,
+}
+
+/// Samples of every server's load, one column per server in `servers` order.
+#[derive(Clone, Debug, Serialize)]
+pub struct TimeSeries {
+    pub servers: Vec<String>,
+    pub samples: Vec<TimeSeriesSample>,
+}
+
+/// Server load after every event up to `time_ms` was processed.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TimeSeriesSample {
+    pub time_ms: u64,
+    /// Requests dispatched to the server and not yet completed.
+    pub in_flight: Vec<u32>,
+    pub active_connections: Vec<u32>,
+    /// Whether the server was serving a request at `time_ms`.
+    pub busy: Vec<bool>,
 }
@@ -0,0 +1,393 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::algorithms::StrategyRegistry;
+use crate::compare::{add_headline_percentiles, ComparisonRow};
+use crate::config::overload_rate;
+use crate::engine::run_simulation_with_registry;
+use crate::error::{Error, Result};
+use crate::models::{AlgoConfig, ParamValue, RequestProfile, SimConfig, TieBreakConfig};
+
+/// Poisson duration used by an `overload_factor` axis when the base scenario
+/// is not already Poisson; matches `--overload-duration-ms`.
+const DEFAULT_OVERLOAD_DURATION_MS: u64 = 1000;
+
+/// One swept setting and the values it takes.
+///
+/// `name` is a dotted path into `SimConfig` (`seed`, `balancers.count`,
+/// `servers.b.latency`; servers are addressed by name or index, and a
+/// trailing `_ms` or `base_..._ms` may be left off a field name), or one of:
+///
+/// * `algo`: the algorithm name
+/// * `algo.<param>`: a parameter of the algorithm
+/// * `overload_factor`: Poisson arrivals at this multiple of total capacity
+#[derive(Clone, Debug, PartialEq)]
+pub struct SweepAxis {
+    pub name: String,
+    pub values: Vec<Value>,
+}
+
+impl SweepAxis {
+    /// Parses `name=values`, where values are a list `[a,b,c]`, an inclusive
+    /// range `start..end step s` (step defaults to 1), or a single value.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let invalid = |reason: &str| Error::InvalidSweepAxis(spec.to_string(), reason.to_string());
+        let (name, values) = spec
+            .split_once('=')
+            .map(|(name, values)| (name.trim(), values.trim()))
+            .filter(|(name, values)| !name.is_empty() && !values.is_empty())
+            .ok_or_else(|| invalid("expected name=values"))?;
+
+        let values = if let Some(list) = values
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            list.split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(parse_value)
+                .collect()
+        } else if let Some((start, rest)) = values.split_once("..") {
+            let (end, step) = match rest.split_once("step") {
+                Some((end, step)) => (end.trim(), step.trim()),
+                None => (rest.trim(), "1"),
+            };
+            range_values(start.trim(), end, step).ok_or_else(|| {
+                invalid("expected start..end [step s] with step > 0 and start <= end")
+            })?
+        } else {
+            vec![parse_value(values)]
+        };
+        if values.is_empty() {
+            return Err(invalid("no values"));
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            values,
+        })
+    }
+}
+
+/// One combination of axis values and the metrics of its run.
+#[derive(Clone, Debug)]
+pub struct SweepRow {
+    /// Values in axis order.
+    pub values: Vec<Value>,
+    pub metrics: ComparisonRow,
+}
+
+/// Expands `axes` into their cartesian product (the first axis varies
+/// slowest) and applies each combination to `base`.
+pub fn expand(base: &SimConfig, axes: &[SweepAxis]) -> Result<Vec<(Vec<Value>, SimConfig)>> {
+    let mut combinations: Vec<Vec<Value>> = vec![Vec::new()];
+    for axis in axes {
+        combinations = combinations
+            .into_iter()
+            .flat_map(|prefix| {
+                axis.values.iter().map(move |value| {
+                    let mut combination = prefix.clone();
+                    combination.push(value.clone());
+                    combination
+                })
+            })
+            .collect();
+    }
+
+    combinations
+        .into_iter()
+        .map(|values| {
+            let config = apply(base, axes, &values)?;
+            Ok((values, config))
+        })
+        .collect()
+}
+
+/// Runs every combination of `axes` on `base`.
+pub fn run_sweep(
+    base: &SimConfig,
+    registry: &StrategyRegistry,
+    axes: &[SweepAxis],
+) -> Result<Vec<SweepRow>> {
+    expand(base, axes)?
+        .into_iter()
+        .map(|(values, mut config)| {
+            add_headline_percentiles(&mut config);
+            let result = run_simulation_with_registry(&config, registry, false)?;
+            Ok(SweepRow {
+                values,
+                metrics: ComparisonRow::from_result(&result),
+            })
+        })
+        .collect()
+}
+
+fn apply(base: &SimConfig, axes: &[SweepAxis], values: &[Value]) -> Result<SimConfig> {
+    let invalid =
+        |axis: &SweepAxis, reason: String| Error::InvalidSweepAxis(axis.name.clone(), reason);
+    let pairs = || axes.iter().zip(values);
+
+    let mut root = serde_json::to_value(base).map_err(|err| Error::ConfigParse(err.to_string()))?;
+    for (axis, value) in pairs() {
+        if is_special(&axis.name) {
+            continue;
+        }
+        let path: Vec<&str> = axis.name.split('.').collect();
+        set_path(&mut root, &path, value.clone()).map_err(|reason| invalid(axis, reason))?;
+        SimConfig::deserialize(&root).map_err(|err| invalid(axis, err.to_string()))?;
+    }
+    let mut config: SimConfig =
+        serde_json::from_value(root).map_err(|err| Error::ConfigParse(err.to_string()))?;
+    if pairs().any(|(axis, _)| axis.name == "seed") {
+        // As with --seed, a swept seed also seeds tie-breaking.
+        config.tie_break = TieBreakConfig::Seeded;
+    }
+
+    for (_, value) in pairs().filter(|(axis, _)| axis.name == "algo") {
+        let name = value
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| value.to_string());
+        if name != config.algo.name() {
+            config.algo = AlgoConfig::new(name);
+        }
+    }
+    for (axis, value) in pairs() {
+        if let Some(param) = axis.name.strip_prefix("algo.") {
+            config.algo.set_param(param, param_value(value));
+        }
+    }
+    for (axis, value) in pairs().filter(|(axis, _)| axis.name == "overload_factor") {
+        let factor = value
+            .as_f64()
+            .filter(|factor| *factor > 0.0)
+            .ok_or_else(|| invalid(axis, format!("expected a factor > 0 (got {})", value)))?;
+        let duration_ms = match config.requests {
+            RequestProfile::Poisson { duration_ms, .. } => duration_ms,
+            _ => DEFAULT_OVERLOAD_DURATION_MS,
+        };
+        config.requests = RequestProfile::Poisson {
+            rate: overload_rate(&config.servers, factor),
+            duration_ms,
+        };
+    }
+    Ok(config)
+}
+
+fn is_special(name: &str) -> bool {
+    name == "algo" || name.starts_with("algo.") || name == "overload_factor"
+}
+
+fn set_path(node: &mut Value, path: &[&str], value: Value) -> std::result::Result<(), String> {
+    let (segment, rest) = path.split_first().ok_or("empty path")?;
+    if node.is_null() {
+        *node = Value::Object(Default::default());
+    }
+    let child = match node {
+        Value::Object(map) => {
+            let key = [
+                segment.to_string(),
+                format!("{}_ms", segment),
+                format!("base_{}_ms", segment),
+            ]
+            .into_iter()
+            .find(|key| map.contains_key(key))
+            .unwrap_or_else(|| segment.to_string());
+            map.entry(key).or_insert(Value::Null)
+        }
+        Value::Array(items) => {
+            let position = items
+                .iter()
+                .position(|item| item.get("name").and_then(Value::as_str) == Some(segment))
+                .or_else(|| segment.parse().ok().filter(|index| *index < items.len()))
+                .ok_or_else(|| format!("no entry '{}'", segment))?;
+            &mut items[position]
+        }
+        _ => return Err(format!("'{}' is not a table or list", segment)),
+    };
+    if rest.is_empty() {
+        *child = value;
+        Ok(())
+    } else {
+        set_path(child, rest, value)
+    }
+}
+
+fn parse_value(text: &str) -> Value {
+    match ParamValue::parse(text) {
+        ParamValue::Bool(value) => Value::from(value),
+        ParamValue::Integer(value) => Value::from(value),
+        ParamValue::Float(value) => Value::from(value),
+        ParamValue::String(value) => Value::from(value),
+    }
+}
+
+fn param_value(value: &Value) -> ParamValue {
+    match value {
+        Value::Bool(flag) => ParamValue::Bool(*flag),
+        Value::Number(number) => match number.as_i64() {
+            Some(integer) => ParamValue::Integer(integer),
+            None => ParamValue::Float(number.as_f64().unwrap_or(f64::NAN)),
+        },
+        Value::String(text) => ParamValue::String(text.clone()),
+        other => ParamValue::String(other.to_string()),
+    }
+}
+
+/// Inclusive range; integer bounds and step give integer values.
+fn range_values(start: &str, end: &str, step: &str) -> Option<Vec<Value>> {
+    if let (Ok(start), Ok(end), Ok(step)) = (
+        start.parse::<i64>(),
+        end.parse::<i64>(),
+        step.parse::<i64>(),
+    ) {
+        if step <= 0 || start > end {
+            return None;
+        }
+        return Some(
+            (start..=end)
+                .step_by(step as usize)
+                .map(Value::from)
+                .collect(),
+        );
+    }
+    let (start, end, step): (f64, f64, f64) =
+        (start.parse().ok()?, end.parse().ok()?, step.parse().ok()?);
+    if !(step > 0.0 && start <= end) {
+        return None;
+    }
+    let count = ((end - start) / step + 1e-9).floor() as usize + 1;
+    Some(
+        (0..count)
+            .map(|i| Value::from(((start + i as f64 * step) * 1e9).round() / 1e9))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support;
+
+    fn scenario() -> SimConfig {
+        SimConfig {
+            tie_break: TieBreakConfig::Seeded,
+            ..test_support::scenario(
+                RequestProfile::FixedCount(20),
+                AlgoConfig::new("round-robin"),
+            )
+        }
+    }
+
+    #[test]
+    fn axis_parses_lists_ranges_and_single_values() {
+        let list = SweepAxis::parse("algo=[round-robin, least-connections]").expect("list");
+        assert_eq!(list.name, "algo");
+        assert_eq!(
+            list.values,
+            vec![Value::from("round-robin"), Value::from("least-connections")]
+        );
+
+        let floats = SweepAxis::parse("overload_factor=0.5..1.5 step 0.25").expect("range");
+        assert_eq!(
+            floats.values,
+            [0.5, 0.75, 1.0, 1.25, 1.5].map(Value::from).to_vec()
+        );
+        let tenths = SweepAxis::parse("overload_factor=0.5..1.5 step 0.1").expect("range");
+        assert_eq!(tenths.values.len(), 11);
+        assert_eq!(tenths.values[2], Value::from(0.7));
+
+        let integers = SweepAxis::parse("servers.b.latency=10..40 step 10").expect("range");
+        assert_eq!(integers.values, [10, 20, 30, 40].map(Value::from).to_vec());
+        assert_eq!(
+            SweepAxis::parse("seed=3").expect("single").values,
+            vec![Value::from(3)]
+        );
+
+        for spec in ["seed", "seed=5..1", "seed=1..5 step 0", "seed=[]"] {
+            assert!(
+                matches!(SweepAxis::parse(spec), Err(Error::InvalidSweepAxis(..))),
+                "{}",
+                spec
+            );
+        }
+    }
+
+    #[test]
+    fn expand_applies_each_combination() {
+        let axes = [
+            SweepAxis::parse("algo=[round-robin,join-shortest-queue]").unwrap(),
+            SweepAxis::parse("servers.b.latency=[20,40]").unwrap(),
+            SweepAxis::parse("algo.d=[3]").unwrap(),
+        ];
+        let variants = expand(&scenario(), &axes[..2]).expect("expand");
+
+        assert_eq!(variants.len(), 4);
+        assert_eq!(variants[1].1.algo.name(), "round-robin");
+        assert_eq!(variants[1].1.servers[1].base_latency_ms, 40);
+        assert_eq!(variants[2].1.algo.name(), "join-shortest-queue");
+        assert_eq!(variants[2].1.servers[1].base_latency_ms, 20);
+
+        let with_param = expand(&scenario(), &axes[1..]).expect("expand");
+        assert_eq!(
+            with_param[0].1.algo.param("d"),
+            Some(&ParamValue::Integer(3))
+        );
+    }
+
+    #[test]
+    fn overload_factor_axis_uses_swept_capacity() {
+        let axes = [
+            SweepAxis::parse("servers.a.latency=[10,20]").unwrap(),
+            SweepAxis::parse("overload_factor=[2]").unwrap(),
+        ];
+        let variants = expand(&scenario(), &axes).expect("expand");
+
+        let rates: Vec<f64> = variants
+            .iter()
+            .map(|(_, config)| match config.requests {
+                RequestProfile::Poisson { rate, duration_ms } => {
+                    assert_eq!(duration_ms, DEFAULT_OVERLOAD_DURATION_MS);
+                    rate.round()
+                }
+                _ => panic!("overload_factor should switch to Poisson arrivals"),
+            })
+            .collect();
+        // Twice the pool's capacity: 2 * (1000/10 + 1000/20 + 1000/30), then
+        // with a at 20 ms.
+        assert_eq!(rates, vec![367.0, 267.0]);
+    }
+
+    #[test]
+    fn invalid_paths_and_values_are_rejected() {
+        for spec in ["servers.z.latency=[5]", "servers.a.latency=[fast]"] {
+            let axis = SweepAxis::parse(spec).unwrap();
+            assert!(
+                matches!(
+                    expand(&scenario(), &[axis]),
+                    Err(Error::InvalidSweepAxis(..))
+                ),
+                "{}",
+                spec
+            );
+        }
+    }
+
+    #[test]
+    fn sweep_runs_one_row_per_combination() {
+        let registry = StrategyRegistry::builtin();
+        let axes = [
+            SweepAxis::parse("algo=[round-robin,least-response-time]").unwrap(),
+            SweepAxis::parse("overload_factor=[0.5,1.2]").unwrap(),
+        ];
+        let rows = run_sweep(&scenario(), &registry, &axes).expect("sweep should run");
+
+        assert_eq!(rows.len(), 4);
+        assert_eq!(
+            rows[3].values,
+            vec![Value::from("least-response-time"), Value::from(1.2)]
+        );
+        assert_eq!(rows[3].metrics.algo, "least-response-time");
+        assert!(rows[1].metrics.p99_ms > rows[0].metrics.p99_ms);
+    }
+}
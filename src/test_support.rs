@@ -0,0 +1,16 @@
+//! Fixtures shared by the unit tests of the compare, replication and sweep
+//! drivers.
+
+use crate::models::{AlgoConfig, RequestProfile, ServerConfig, SimConfig};
+
+/// Servers `a`, `b` and `c` at 10, 20 and 30 ms, seeded with 42.
+pub(crate) fn scenario(requests: RequestProfile, algo: AlgoConfig) -> SimConfig {
+    let servers = [("a", 10), ("b", 20), ("c", 30)]
+        .into_iter()
+        .map(|(name, latency)| ServerConfig::new(name, latency))
+        .collect();
+    SimConfig {
+        seed: Some(42),
+        ..SimConfig::new(servers, requests, algo)
+    }
+}
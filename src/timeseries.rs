@@ -0,0 +1,135 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+use crate::models::TimeSeriesSampling;
+use crate::state::{ServerState, TimeSeries, TimeSeriesSample};
+
+/// Samples every server's load while the engine runs. The engine calls
+/// `advance` with the time of each event before processing it, so a sample
+/// taken at `time_ms` sees every event up to and including `time_ms`.
+#[derive(Clone, Debug)]
+pub struct TimeSeriesRecorder {
+    sampling: TimeSeriesSampling,
+    next_sample_ms: u64,
+    /// Pending sample times in `events` mode: processed event times and the
+    /// start and end of every service.
+    change_times: BinaryHeap<Reverse<u64>>,
+    /// Service intervals not yet over, per server, in start order.
+    services: Vec<VecDeque<(u64, u64)>>,
+    servers: Vec<String>,
+    samples: Vec<TimeSeriesSample>,
+}
+
+impl TimeSeriesRecorder {
+    pub fn new(sampling: TimeSeriesSampling, servers: &[ServerState]) -> Self {
+        Self {
+            sampling,
+            next_sample_ms: 0,
+            change_times: BinaryHeap::new(),
+            services: vec![VecDeque::new(); servers.len()],
+            servers: servers.iter().map(|server| server.name.clone()).collect(),
+            samples: Vec::new(),
+        }
+    }
+
+    /// Takes every sample due before `time_ms` from the current server state.
+    pub fn advance(&mut self, time_ms: u64, servers: &[ServerState]) {
+        match self.sampling {
+            TimeSeriesSampling::Interval(interval_ms) => {
+                while self.next_sample_ms < time_ms {
+                    self.sample(self.next_sample_ms, servers);
+                    self.next_sample_ms += interval_ms;
+                }
+            }
+            TimeSeriesSampling::Events => {
+                while let Some(&Reverse(sample_ms)) = self.change_times.peek() {
+                    if sample_ms >= time_ms {
+                        break;
+                    }
+                    while self.change_times.peek() == Some(&Reverse(sample_ms)) {
+                        self.change_times.pop();
+                    }
+                    self.sample(sample_ms, servers);
+                }
+            }
+        }
+    }
+
+    /// Notes that an event at `time_ms` was processed.
+    pub fn on_event(&mut self, time_ms: u64) {
+        if self.sampling == TimeSeriesSampling::Events {
+            self.change_times.push(Reverse(time_ms));
+        }
+    }
+
+    /// Notes a request served by `server_index` from `started_at` to
+    /// `completed_at`.
+    pub fn on_service(&mut self, server_index: usize, started_at: u64, completed_at: u64) {
+        self.services[server_index].push_back((started_at, completed_at));
+        if self.sampling == TimeSeriesSampling::Events {
+            self.change_times.push(Reverse(started_at));
+            self.change_times.push(Reverse(completed_at));
+        }
+    }
+
+    /// Cuts the server's service intervals short at `time_ms`, when it goes
+    /// down and its queued work fails.
+    pub fn on_server_down(&mut self, server_index: usize, time_ms: u64) {
+        let services = &mut self.services[server_index];
+        services.retain(|&(started_at, _)| started_at < time_ms);
+        for service in services.iter_mut() {
+            service.1 = service.1.min(time_ms);
+        }
+        if self.sampling == TimeSeriesSampling::Events {
+            self.change_times.push(Reverse(time_ms));
+        }
+    }
+
+    /// Takes the samples due up to and including `end_ms`, the time of the
+    /// last event.
+    pub fn finish(mut self, end_ms: u64, servers: &[ServerState]) -> TimeSeries {
+        self.advance(end_ms + 1, servers);
+        TimeSeries {
+            servers: self.servers,
+            samples: self.samples,
+        }
+    }
+
+    fn sample(&mut self, time_ms: u64, servers: &[ServerState]) {
+        let busy = self
+            .services
+            .iter_mut()
+            .map(|services| {
+                while services
+                    .front()
+                    .is_some_and(|&(_, completed_at)| completed_at <= time_ms)
+                {
+                    services.pop_front();
+                }
+                services
+                    .front()
+                    .is_some_and(|&(started_at, _)| started_at <= time_ms)
+            })
+            .collect();
+        let sample = TimeSeriesSample {
+            time_ms,
+            in_flight: servers.iter().map(|server| server.in_flight).collect(),
+            active_connections: servers
+                .iter()
+                .map(|server| server.active_connections)
+                .collect(),
+            busy,
+        };
+        if self.sampling == TimeSeriesSampling::Events {
+            let unchanged = self.samples.last().is_some_and(|last| {
+                last.in_flight == sample.in_flight
+                    && last.active_connections == sample.active_connections
+                    && last.busy == sample.busy
+            });
+            if unchanged {
+                return;
+            }
+        }
+        self.samples.push(sample);
+    }
+}
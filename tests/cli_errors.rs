@@ -69,3 +69,181 @@ fn duplicate_server_names_fail() {
         .failure()
         .stderr(contains("Error: duplicate server name 'a'"));
 }
+
+#[test]
+fn unknown_algorithm_fails() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "run",
+        "--algo",
+        "fastest",
+        "--server",
+        "a:10",
+        "--requests",
+        "1",
+    ]);
+    cmd.assert()
+        .failure()
+        .stderr(contains("Error: unknown algorithm 'fastest'"));
+}
+
+#[test]
+fn invalid_algo_param_fails() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "run",
+        "--algo",
+        "join-shortest-queue",
+        "--algo-param",
+        "d=0",
+        "--server",
+        "a:10",
+        "--requests",
+        "1",
+    ]);
+    cmd.assert().failure().stderr(contains(
+        "Error: invalid parameter for 'join-shortest-queue': d must be >= 1 (got 0)",
+    ));
+}
+
+#[test]
+fn zero_replications_fails() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "run",
+        "--algo",
+        "round-robin",
+        "--server",
+        "a:10",
+        "--requests",
+        "1",
+        "--replications",
+        "0",
+    ]);
+    cmd.assert()
+        .failure()
+        .stderr(contains("Error: --replications must be greater than 0"));
+}
+
+#[test]
+fn invalid_percentile_fails() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "run",
+        "--algo",
+        "round-robin",
+        "--server",
+        "a:10",
+        "--requests",
+        "1",
+        "--percentiles",
+        "p50,p0",
+    ]);
+    cmd.assert()
+        .failure()
+        .stderr(contains("Error: invalid percentile 'p0'"));
+}
+
+#[test]
+fn hdr_digits_out_of_range_fails() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "run",
+        "--algo",
+        "round-robin",
+        "--server",
+        "a:10",
+        "--requests",
+        "1",
+        "--latency-backend",
+        "hdr",
+        "--hdr-digits",
+        "9",
+    ]);
+    cmd.assert().failure().stderr(contains(
+        "Error: significant digits must be in 1..=5 (got 9)",
+    ));
+}
+
+#[test]
+fn event_log_with_replications_fails() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "run",
+        "--algo",
+        "round-robin",
+        "--server",
+        "a:10",
+        "--requests",
+        "1",
+        "--replications",
+        "2",
+        "--event-log",
+        "events.ndjson",
+    ]);
+    cmd.assert().failure().stderr(contains(
+        "Error: --event-log cannot be combined with --replications",
+    ));
+}
+
+#[test]
+fn sync_delay_without_sync_interval_fails() {
+    for extra in [&[][..], &["--balancers", "2"][..]] {
+        let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+        cmd.args([
+            "run",
+            "--algo",
+            "round-robin",
+            "--server",
+            "a:10",
+            "--requests",
+            "1",
+            "--sync-delay-ms",
+            "5",
+        ]);
+        cmd.args(extra);
+        cmd.assert().failure().stderr(contains(
+            "Error: --sync-delay-ms requires --balancers and --sync-interval-ms",
+        ));
+    }
+}
+
+#[test]
+fn overlapping_outages_fail() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "run",
+        "--algo",
+        "round-robin",
+        "--servers",
+        "a:10,b:20",
+        "--outage",
+        "a=0..50",
+        "--outage",
+        "a=10..80",
+        "--requests",
+        "10",
+    ]);
+    cmd.assert()
+        .failure()
+        .stderr(contains("Error: outages overlap or touch in 'a'"));
+}
+
+#[test]
+fn timeseries_out_without_timeseries_fails() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "run",
+        "--algo",
+        "round-robin",
+        "--servers",
+        "a:10",
+        "--requests",
+        "3",
+        "--timeseries-out",
+        "samples.csv",
+    ]);
+    cmd.assert()
+        .failure()
+        .stderr(contains("Error: --timeseries-out needs --timeseries"));
+}
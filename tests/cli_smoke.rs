@@ -8,8 +8,8 @@ fn summary_round_robin_is_stable() {
         "tie_break: seeded(42)\n",
         "duration_ms: 21\n",
         "Summary:\n",
-        "a: 2 requests (avg response: 14ms)\n",
-        "b: 1 requests (avg response: 20ms)\n",
+        "a: 2 requests (avg response: 14ms, p50/p95/p99: 10/18/18ms, avg wait: 4ms, avg service: 10ms, max queue: 1, busy: 20ms)\n",
+        "b: 1 requests (avg response: 20ms, p50/p95/p99: 20/20/20ms, avg wait: 0ms, avg service: 20ms, max queue: 0, busy: 20ms)\n",
     );
 
     let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
@@ -38,8 +38,8 @@ fn summary_least_response_time_is_stable() {
         "tie_break: seeded(7)\n",
         "duration_ms: 20\n",
         "Summary:\n",
-        "fast: 2 requests (avg response: 14ms)\n",
-        "slow: 0 requests (avg response: 0ms)\n",
+        "fast: 2 requests (avg response: 14ms, p50/p95/p99: 10/19/19ms, avg wait: 4ms, avg service: 10ms, max queue: 1, busy: 20ms)\n",
+        "slow: 0 requests (avg response: 0ms, p50/p95/p99: -, avg wait: 0ms, avg service: 0ms, max queue: 0, busy: 0ms)\n",
     );
 
     let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
@@ -68,9 +68,9 @@ fn summary_preserves_input_order() {
         "tie_break: stable\n",
         "duration_ms: 10\n",
         "Summary:\n",
-        "z: 1 requests (avg response: 10ms)\n",
-        "a: 0 requests (avg response: 0ms)\n",
-        "m: 0 requests (avg response: 0ms)\n",
+        "z: 1 requests (avg response: 10ms, p50/p95/p99: 10/10/10ms, avg wait: 0ms, avg service: 10ms, max queue: 0, busy: 10ms)\n",
+        "a: 0 requests (avg response: 0ms, p50/p95/p99: -, avg wait: 0ms, avg service: 0ms, max queue: 0, busy: 0ms)\n",
+        "m: 0 requests (avg response: 0ms, p50/p95/p99: -, avg wait: 0ms, avg service: 0ms, max queue: 0, busy: 0ms)\n",
     );
 
     let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
@@ -99,9 +99,9 @@ fn summary_preserves_input_order_for_least_connections() {
         "tie_break: seeded(11)\n",
         "duration_ms: 42\n",
         "Summary:\n",
-        "first: 1 requests (avg response: 10ms)\n",
-        "second: 2 requests (avg response: 29ms)\n",
-        "third: 1 requests (avg response: 30ms)\n",
+        "first: 1 requests (avg response: 10ms, p50/p95/p99: 10/10/10ms, avg wait: 0ms, avg service: 10ms, max queue: 0, busy: 10ms)\n",
+        "second: 2 requests (avg response: 29ms, p50/p95/p99: 20/39/39ms, avg wait: 9ms, avg service: 20ms, max queue: 1, busy: 40ms)\n",
+        "third: 1 requests (avg response: 30ms, p50/p95/p99: 30/30/30ms, avg wait: 0ms, avg service: 30ms, max queue: 0, busy: 30ms)\n",
     );
 
     let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
@@ -136,8 +136,8 @@ fn full_output_least_response_time_includes_scores() {
         "Request 2 -> b (score: 11ms)\n",
         "Request 3 -> a (score: 20ms)\n",
         "Summary:\n",
-        "a: 2 requests (avg response: 14ms)\n",
-        "b: 1 requests (avg response: 10ms)\n",
+        "a: 2 requests (avg response: 14ms, p50/p95/p99: 10/18/18ms, avg wait: 4ms, avg service: 10ms, max queue: 1, busy: 20ms)\n",
+        "b: 1 requests (avg response: 10ms, p50/p95/p99: 10/10/10ms, avg wait: 0ms, avg service: 10ms, max queue: 0, busy: 10ms)\n",
     );
 
     let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
@@ -170,8 +170,8 @@ fn full_output_round_robin_omits_scores() {
         "Request 2 -> b\n",
         "Request 3 -> a\n",
         "Summary:\n",
-        "a: 2 requests (avg response: 14ms)\n",
-        "b: 1 requests (avg response: 20ms)\n",
+        "a: 2 requests (avg response: 14ms, p50/p95/p99: 10/18/18ms, avg wait: 4ms, avg service: 10ms, max queue: 1, busy: 20ms)\n",
+        "b: 1 requests (avg response: 20ms, p50/p95/p99: 20/20/20ms, avg wait: 0ms, avg service: 20ms, max queue: 0, busy: 20ms)\n",
     );
 
     let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
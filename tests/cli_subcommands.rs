@@ -1,12 +1,22 @@
 use predicates::str::diff;
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[test]
 fn list_algorithms_prints_supported_values() {
     let expected = concat!(
         "round-robin\n",
         "weighted-round-robin\n",
+        "smooth-weighted-round-robin\n",
         "least-connections\n",
         "least-response-time\n",
+        "weighted-least-connections\n",
+        "weighted-least-response-time\n",
+        "join-idle-queue\n",
+        "join-shortest-queue\n",
+        "random\n",
+        "weighted-random\n",
+        "consistent-hash\n",
     );
 
     let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
@@ -14,6 +24,15 @@ fn list_algorithms_prints_supported_values() {
     cmd.assert().success().stdout(diff(expected));
 }
 
+#[test]
+fn list_algorithms_verbose_prints_descriptions() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args(["list-algorithms", "--verbose"]);
+    cmd.assert().success().stdout(predicates::str::contains(
+        "least-connections\n  Routes to the backend with the fewest active requests.\n  aliases: lc\n",
+    ));
+}
+
 #[test]
 fn show_config_prints_parsed_configuration() {
     let expected = concat!(
@@ -39,3 +58,421 @@ fn show_config_prints_parsed_configuration() {
     ]);
     cmd.assert().success().stdout(diff(expected));
 }
+
+#[test]
+fn show_config_prints_zones_and_locality() {
+    let expected = concat!(
+        "Algorithm: least-connections\n",
+        "Requests: 3\n",
+        "Tie-break: stable\n",
+        "Locality: zone east (threshold spillover at 50% busy)\n",
+        "Servers:\n",
+        "- api (latency: 10ms, weight: 1, zone: east)\n",
+        "- db (latency: 20ms, weight: 1, zone: west)\n",
+    );
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "show-config",
+        "--algo",
+        "least-connections",
+        "--servers",
+        "api:10,db:20",
+        "--server-zone",
+        "api=east",
+        "--server-zone",
+        "db=west",
+        "--zone",
+        "east",
+        "--spillover-threshold",
+        "0.5",
+        "--requests",
+        "3",
+    ]);
+    cmd.assert().success().stdout(diff(expected));
+}
+
+#[test]
+fn show_config_prints_priority_tiers_and_outages() {
+    let expected = concat!(
+        "Algorithm: round-robin\n",
+        "Requests: 3\n",
+        "Tie-break: stable\n",
+        "Failover: tier needs 50% healthy\n",
+        "Servers:\n",
+        "- primary (latency: 10ms, weight: 1, outages: 100..200ms)\n",
+        "- backup (latency: 20ms, weight: 1, priority: 1)\n",
+    );
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "show-config",
+        "--algo",
+        "round-robin",
+        "--servers",
+        "primary:10,backup:20",
+        "--server-priority",
+        "backup=1",
+        "--outage",
+        "primary=100..200",
+        "--healthy-threshold",
+        "0.5",
+        "--requests",
+        "3",
+    ]);
+    cmd.assert().success().stdout(diff(expected));
+}
+
+#[test]
+fn show_config_prints_keys_and_algo_params() {
+    let expected = concat!(
+        "Algorithm: consistent-hash (epsilon=0.5)\n",
+        "Requests: 3\n",
+        "Tie-break: stable\n",
+        "Keys: 64 (zipf(1.1))\n",
+        "Cache: 8 keys per server, hit latency 1ms\n",
+        "Servers:\n",
+        "- a (latency: 10ms, weight: 1)\n",
+        "- b (latency: 20ms, weight: 1)\n",
+    );
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "show-config",
+        "--algo",
+        "consistent-hash",
+        "--servers",
+        "a:10,b:20",
+        "--keys",
+        "64",
+        "--key-zipf",
+        "1.1",
+        "--algo-param",
+        "epsilon=0.5",
+        "--cache-size",
+        "8",
+        "--requests",
+        "3",
+    ]);
+    cmd.assert().success().stdout(diff(expected));
+}
+
+#[test]
+fn show_config_prints_admission_limits() {
+    let expected = concat!(
+        "Algorithm: least-connections\n",
+        "Requests: 3\n",
+        "Tie-break: stable\n",
+        "Admission: max 8 in flight, rate 200/s (burst 10), adaptive limit 1..1000 backing off above 50ms\n",
+        "Servers:\n",
+        "- a (latency: 10ms, weight: 1)\n",
+    );
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "show-config",
+        "--algo",
+        "least-connections",
+        "--servers",
+        "a:10",
+        "--max-in-flight",
+        "8",
+        "--rate-limit",
+        "200",
+        "--rate-limit-burst",
+        "10",
+        "--adaptive-limit-ms",
+        "50",
+        "--requests",
+        "3",
+    ]);
+    cmd.assert().success().stdout(diff(expected));
+}
+
+#[test]
+fn show_config_prints_measurement_window() {
+    let expected = concat!(
+        "Algorithm: round-robin\n",
+        "Requests: poisson(rate=150, duration_ms=2000)\n",
+        "Tie-break: stable\n",
+        "Measurement: warmup 500ms, window 1000ms\n",
+        "Servers:\n",
+        "- a (latency: 10ms, weight: 1)\n",
+        "- b (latency: 20ms, weight: 1)\n",
+    );
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "show-config",
+        "--algo",
+        "round-robin",
+        "--servers",
+        "a:10,b:20",
+        "--overload",
+        "--overload-factor",
+        "1",
+        "--overload-duration-ms",
+        "2000",
+        "--warmup-ms",
+        "500",
+        "--measure-window-ms",
+        "1000",
+    ]);
+    cmd.assert().success().stdout(diff(expected));
+}
+
+#[test]
+fn compare_prints_table_for_chosen_algorithms() {
+    let expected = concat!(
+        "algo               p95_ms  p99_ms  throughput_rps  jain_fairness  avg_wait_ms\n",
+        "round-robin           138     165          109.89         0.9950           45\n",
+        "least-connections     137     163          110.50         0.9950           45\n",
+    );
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "compare",
+        "--algos",
+        "round-robin,least-connections",
+        "--servers",
+        "a:10,b:20,c:30",
+        "--requests",
+        "20",
+        "--seed",
+        "42",
+    ]);
+    cmd.assert().success().stdout(diff(expected));
+}
+
+#[test]
+fn compare_prints_markdown_table() {
+    let expected = concat!(
+        "| Policy | p95 (ms) | p99 (ms) | Throughput (rps) | Fairness | Avg wait (ms) |\n",
+        "| ------ | -------: | -------: | ---------------: | -------: | ------------: |\n",
+        "| round-robin | 138 | 165 | 109.89 | 0.9950 | 45 |\n",
+    );
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "compare",
+        "--algos",
+        "round-robin",
+        "--servers",
+        "a:10,b:20,c:30",
+        "--requests",
+        "20",
+        "--seed",
+        "42",
+        "--format",
+        "markdown",
+    ]);
+    cmd.assert().success().stdout(diff(expected));
+}
+
+#[test]
+fn sweep_writes_one_csv_row_per_combination() {
+    let expected = concat!(
+        "servers.c.latency,algo,p95_ms,p99_ms,throughput_rps,jain_fairness,avg_wait_ms\n",
+        "30,round-robin,138,165,109.89,0.9950,45\n",
+        "60,round-robin,288,345,55.25,0.9950,67\n",
+        "30,least-connections,137,163,110.50,0.9950,45\n",
+        "60,least-connections,287,343,55.40,0.9950,68\n",
+    );
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "sweep",
+        "algo=[round-robin,least-connections]",
+        "servers.c.latency=[30,60]",
+        "--servers",
+        "a:10,b:20,c:30",
+        "--requests",
+        "20",
+        "--seed",
+        "42",
+        "--format",
+        "csv",
+    ]);
+    cmd.assert().success().stdout(diff(expected));
+}
+
+#[test]
+fn sweep_accepts_algorithm_aliases_in_lists() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "sweep",
+        "algo=[rr,lc,lrt]",
+        "--servers",
+        "a:10,b:20,c:30",
+        "--requests",
+        "20",
+        "--seed",
+        "42",
+        "--format",
+        "csv",
+    ]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let algos: Vec<String> = String::from_utf8(output)
+        .expect("utf-8 output")
+        .lines()
+        .skip(1)
+        .map(|line| line.split(',').next().unwrap_or_default().to_string())
+        .collect();
+    assert_eq!(algos, vec!["rr", "lc", "lrt"]);
+}
+
+#[test]
+fn run_replications_prints_metric_summary() {
+    let expected = concat!(
+        "metric,mean,std_dev,ci95_low,ci95_high,samples\n",
+        "avg_network_ms,0.000,0.000,0.000,0.000,3\n",
+        "avg_wait_ms,26.000,0.000,26.000,26.000,3\n",
+        "jain_fairness,1.000,0.000,1.000,1.000,3\n",
+        "per_server_utilization.a.utilization_pct,49.500,0.000,49.500,49.500,3\n",
+        "per_server_utilization.b.utilization_pct,99.010,0.000,99.010,99.010,3\n",
+        "response_time.p95_ms,92.000,0.000,92.000,92.000,3\n",
+        "response_time.p99_ms,92.000,0.000,92.000,92.000,3\n",
+        "throughput_rps,99.010,0.000,99.010,99.010,3\n",
+    );
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "run",
+        "--algo",
+        "round-robin",
+        "--servers",
+        "a:10,b:20",
+        "--requests",
+        "10",
+        "--seed",
+        "1",
+        "--replications",
+        "3",
+        "--format",
+        "csv",
+    ]);
+    cmd.assert().success().stdout(diff(expected));
+}
+
+#[test]
+fn run_timeseries_out_writes_csv_samples() {
+    let mut path = std::env::temp_dir();
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("time should be available")
+        .as_nanos();
+    path.push(format!("lb-timeseries-{}.csv", nanos));
+    let expected = concat!(
+        "time_ms,server,in_flight,active_connections,busy\n",
+        "0,a,1,1,1\n",
+        "0,b,0,0,0\n",
+        "20,a,1,1,1\n",
+        "20,b,3,3,1\n",
+        "40,a,0,0,0\n",
+        "40,b,3,3,1\n",
+        "60,a,0,0,0\n",
+        "60,b,2,2,1\n",
+        "80,a,0,0,0\n",
+        "80,b,2,2,1\n",
+        "100,a,0,0,0\n",
+        "100,b,1,1,1\n",
+        "120,a,0,0,0\n",
+        "120,b,1,1,1\n",
+    );
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "run",
+        "--algo",
+        "round-robin",
+        "--servers",
+        "a:10,b:40",
+        "--requests",
+        "6",
+        "--timeseries",
+        "20",
+        "--timeseries-out",
+        path.to_str().unwrap(),
+        "--format",
+        "csv",
+    ]);
+    // The CSV on stdout keeps its headline schema whatever is sampled.
+    cmd.assert().success().stdout(predicates::str::starts_with(
+        "algo,p95_ms,p99_ms,throughput_rps,jain_fairness,avg_wait_ms\n",
+    ));
+
+    let samples = fs::read_to_string(&path).expect("time series should be written");
+    assert_eq!(samples, expected);
+}
+
+#[test]
+fn run_event_log_streams_ndjson_events() {
+    let mut path = std::env::temp_dir();
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("time should be available")
+        .as_nanos();
+    path.push(format!("lb-events-{}.ndjson", nanos));
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "run",
+        "--algo",
+        "least-response-time",
+        "--servers",
+        "a:10,b:20",
+        "--requests",
+        "3",
+        "--summary",
+        "--event-log",
+        path.to_str().unwrap(),
+    ]);
+    cmd.assert().success();
+
+    let expected = concat!(
+        r#"{"time_ms":0,"kind":"request_arrival","request_id":1,"balancer_id":0,"server_id":0,"server":"a","score":10,"started_at":0,"completed_at":10}"#,
+        "\n",
+        r#"{"time_ms":1,"kind":"request_arrival","request_id":2,"balancer_id":0,"server_id":0,"server":"a","score":20,"started_at":10,"completed_at":20}"#,
+        "\n",
+        r#"{"time_ms":2,"kind":"request_arrival","request_id":3,"balancer_id":0,"server_id":1,"server":"b","score":22,"started_at":2,"completed_at":22}"#,
+        "\n",
+        r#"{"time_ms":10,"kind":"request_complete","request_id":1,"balancer_id":0,"server_id":0,"server":"a","latency_ms":10}"#,
+        "\n",
+        r#"{"time_ms":20,"kind":"request_complete","request_id":2,"balancer_id":0,"server_id":0,"server":"a","latency_ms":19}"#,
+        "\n",
+        r#"{"time_ms":22,"kind":"request_complete","request_id":3,"balancer_id":0,"server_id":1,"server":"b","latency_ms":20}"#,
+        "\n",
+    );
+    let log = fs::read_to_string(&path).expect("event log should be written");
+    assert_eq!(log, expected);
+}
+
+#[test]
+fn run_event_log_leaves_assignments_out_of_stdout() {
+    let mut path = std::env::temp_dir();
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("time should be available")
+        .as_nanos();
+    path.push(format!("lb-events-human-{}.ndjson", nanos));
+
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "run",
+        "--algo",
+        "round-robin",
+        "--servers",
+        "a:10,b:20",
+        "--requests",
+        "3",
+        "--event-log",
+        path.to_str().unwrap(),
+    ]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let stdout = String::from_utf8(output).expect("utf-8 output");
+    assert!(!stdout.contains("Request 1 ->"), "{}", stdout);
+    assert!(stdout.contains("Summary:"), "{}", stdout);
+
+    let log = fs::read_to_string(&path).expect("event log should be written");
+    assert_eq!(log.matches("\"kind\":\"request_arrival\"").count(), 3);
+}
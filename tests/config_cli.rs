@@ -33,8 +33,8 @@ servers = [
         "tie_break: seeded(42)\n",
         "duration_ms: 21\n",
         "Summary:\n",
-        "a: 2 requests (avg response: 14ms)\n",
-        "b: 1 requests (avg response: 20ms)\n",
+        "a: 2 requests (avg response: 14ms, p50/p95/p99: 10/18/18ms, avg wait: 4ms, avg service: 10ms, max queue: 1, busy: 20ms)\n",
+        "b: 1 requests (avg response: 20ms, p50/p95/p99: 20/20/20ms, avg wait: 0ms, avg service: 20ms, max queue: 0, busy: 20ms)\n",
     );
     let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
     cmd.args(["run", "--config", path.to_str().unwrap(), "--summary"]);
@@ -49,8 +49,8 @@ fn repeatable_server_flag_parses() {
         "tie_break: stable\n",
         "duration_ms: 21\n",
         "Summary:\n",
-        "api: 1 requests (avg response: 10ms)\n",
-        "db: 1 requests (avg response: 20ms)\n",
+        "api: 1 requests (avg response: 10ms, p50/p95/p99: 10/10/10ms, avg wait: 0ms, avg service: 10ms, max queue: 0, busy: 10ms)\n",
+        "db: 1 requests (avg response: 20ms, p50/p95/p99: 20/20/20ms, avg wait: 0ms, avg service: 20ms, max queue: 0, busy: 20ms)\n",
     );
 
     let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
@@ -77,8 +77,8 @@ fn empty_servers_csv_with_server_entries_succeeds() {
         "tie_break: stable\n",
         "duration_ms: 21\n",
         "Summary:\n",
-        "web: 1 requests (avg response: 10ms)\n",
-        "cache: 1 requests (avg response: 20ms)\n",
+        "web: 1 requests (avg response: 10ms, p50/p95/p99: 10/10/10ms, avg wait: 0ms, avg service: 10ms, max queue: 0, busy: 10ms)\n",
+        "cache: 1 requests (avg response: 20ms, p50/p95/p99: 20/20/20ms, avg wait: 0ms, avg service: 20ms, max queue: 0, busy: 20ms)\n",
     );
 
     let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
@@ -98,3 +98,239 @@ fn empty_servers_csv_with_server_entries_succeeds() {
     ]);
     cmd.assert().success().stdout(diff(expected));
 }
+
+#[test]
+fn config_file_toml_algo_table_sets_params() {
+    let config = r#"
+algo = { kind = "join-shortest-queue", d = 3 }
+requests = 3
+servers = [
+  { name = "a", base_latency_ms = 10 },
+  { name = "b", base_latency_ms = 20 }
+]
+"#;
+    let path = write_temp_config(config, "toml");
+
+    let expected = concat!(
+        "Algorithm: join-shortest-queue (d=4)\n",
+        "Requests: 3\n",
+        "Tie-break: stable\n",
+        "Servers:\n",
+        "- a (latency: 10ms, weight: 1)\n",
+        "- b (latency: 20ms, weight: 1)\n",
+    );
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "show-config",
+        "--config",
+        path.to_str().unwrap(),
+        "--algo-param",
+        "d=4",
+    ]);
+    cmd.assert().success().stdout(diff(expected));
+}
+
+#[test]
+fn config_file_toml_metrics_table_sets_percentiles() {
+    let config = r#"
+algo = "round-robin"
+requests = 3
+servers = [{ name = "a", base_latency_ms = 10 }]
+
+[metrics]
+percentiles = ["p50", 99.9, "max"]
+distribution = "histogram"
+"#;
+    let path = write_temp_config(config, "toml");
+
+    let expected = concat!(
+        "Algorithm: round-robin\n",
+        "Requests: 3\n",
+        "Tie-break: stable\n",
+        "Metrics: p50, p99.9, max, histogram of response times\n",
+        "Servers:\n",
+        "- a (latency: 10ms, weight: 1)\n",
+    );
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args(["show-config", "--config", path.to_str().unwrap()]);
+    cmd.assert().success().stdout(diff(expected));
+}
+
+#[test]
+fn config_file_json_algo_table_sets_params() {
+    let config = r#"{
+  "algo": { "kind": "consistent-hash", "epsilon": 0.1, "virtual_nodes": 40 },
+  "requests": 3,
+  "servers": [{ "name": "a", "base_latency_ms": 10 }]
+}"#;
+    let path = write_temp_config(config, "json");
+
+    let expected = concat!(
+        "Algorithm: consistent-hash (epsilon=0.1, virtual_nodes=40)\n",
+        "Requests: 3\n",
+        "Tie-break: stable\n",
+        "Servers:\n",
+        "- a (latency: 10ms, weight: 1)\n",
+    );
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args(["show-config", "--config", path.to_str().unwrap()]);
+    cmd.assert().success().stdout(diff(expected));
+}
+
+#[cfg(feature = "scripting")]
+#[test]
+fn script_strategy_runs_from_cli() {
+    let script = r#"
+fn select(servers, time_ms) {
+    let last = servers.len() - 1;
+    #{ index: last, score: servers[last].in_flight }
+}
+"#;
+    let path = write_temp_config(script, "rhai");
+
+    let expected = concat!(
+        "Metadata:\n",
+        "algo: last\n",
+        "tie_break: stable\n",
+        "duration_ms: 60\n",
+        "Summary:\n",
+        "a: 0 requests (avg response: 0ms, p50/p95/p99: -, avg wait: 0ms, avg service: 0ms, max queue: 0, busy: 0ms)\n",
+        "b: 3 requests (avg response: 39ms, p50/p95/p99: 39/58/58ms, avg wait: 19ms, avg service: 20ms, max queue: 2, busy: 60ms)\n",
+    );
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "run",
+        "--script",
+        &format!("last={}", path.display()),
+        "--algo",
+        "last",
+        "--servers",
+        "a:10,b:20",
+        "--requests",
+        "3",
+        "--summary",
+    ]);
+    cmd.assert().success().stdout(diff(expected));
+}
+
+#[cfg(not(feature = "scripting"))]
+#[test]
+fn script_flag_requires_scripting_feature() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "run",
+        "--script",
+        "last=last.rhai",
+        "--algo",
+        "last",
+        "--servers",
+        "a:10",
+        "--requests",
+        "1",
+    ]);
+    cmd.assert().failure().stderr(predicates::str::contains(
+        "--script requires lb-sim built with the `scripting` feature",
+    ));
+}
+
+#[cfg(feature = "wasm")]
+#[test]
+fn wasm_plugin_runs_from_cli() {
+    let wasm = wat::parse_str(
+        r#"
+        (module
+          (memory (export "memory") 1)
+          (func (export "lb_abi_version") (result i32) (i32.const 1))
+          (func (export "alloc") (param i32) (result i32) (i32.const 0))
+          (func (export "select") (param i32 i32 i64) (result i64)
+            (i64.extend_i32_u (i32.sub (local.get 1) (i32.const 1)))))
+        "#,
+    )
+    .expect("valid wat");
+    let mut path = std::env::temp_dir();
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("time should be available")
+        .as_nanos();
+    path.push(format!("lb-plugin-{}.wasm", nanos));
+    fs::write(&path, wasm).expect("plugin write should succeed");
+
+    let expected = concat!(
+        "Metadata:\n",
+        "algo: last\n",
+        "tie_break: stable\n",
+        "duration_ms: 60\n",
+        "Summary:\n",
+        "a: 0 requests (avg response: 0ms, p50/p95/p99: -, avg wait: 0ms, avg service: 0ms, max queue: 0, busy: 0ms)\n",
+        "b: 3 requests (avg response: 39ms, p50/p95/p99: 39/58/58ms, avg wait: 19ms, avg service: 20ms, max queue: 2, busy: 60ms)\n",
+    );
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "run",
+        "--plugin",
+        &format!("last={}", path.display()),
+        "--algo",
+        "last",
+        "--servers",
+        "a:10,b:20",
+        "--requests",
+        "3",
+        "--summary",
+    ]);
+    cmd.assert().success().stdout(diff(expected));
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn plugin_flag_requires_wasm_feature() {
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "run",
+        "--plugin",
+        "last=last.wasm",
+        "--algo",
+        "last",
+        "--servers",
+        "a:10",
+        "--requests",
+        "1",
+    ]);
+    cmd.assert().failure().stderr(predicates::str::contains(
+        "--plugin requires lb-sim built with the `wasm` feature",
+    ));
+}
+
+#[test]
+fn sync_delay_flag_applies_to_config_file_balancers() {
+    let config = r#"
+algo = "round-robin"
+requests = 3
+servers = [
+  { name = "a", base_latency_ms = 10 },
+  { name = "b", base_latency_ms = 20 }
+]
+balancers = { count = 2, sync_interval_ms = 10 }
+"#;
+    let path = write_temp_config(config, "toml");
+
+    let expected = concat!(
+        "Metadata:\n",
+        "algo: round-robin\n",
+        "tie_break: stable\n",
+        "duration_ms: 22\n",
+        "balancers: 2 (sync every 10ms, delay 5ms)\n",
+        "Summary:\n",
+        "a: 2 requests (avg response: 14ms, p50/p95/p99: 10/19/19ms, avg wait: 4ms, avg service: 10ms, max queue: 1, busy: 20ms)\n",
+        "b: 1 requests (avg response: 20ms, p50/p95/p99: 20/20/20ms, avg wait: 0ms, avg service: 20ms, max queue: 0, busy: 20ms)\n",
+    );
+    let mut cmd = assert_cmd::cargo::cargo_bin_cmd!("lb-sim");
+    cmd.args([
+        "run",
+        "--config",
+        path.to_str().unwrap(),
+        "--sync-delay-ms",
+        "5",
+        "--summary",
+    ]);
+    cmd.assert().success().stdout(diff(expected));
+}